@@ -0,0 +1,208 @@
+/*! Alignment arithmetic
+
+Rounding addresses and sizes to a power-of-two alignment is short, but easy to
+get subtly wrong: rounding up can overflow, and the bit-masking shortcuts are
+only correct when the alignment is a power of two. The functions in this module
+check both, and the [`AlignExt`] trait provides them as methods on `usize` and
+on raw pointers.
+
+All functions panic if `align` is not a power of two, as `Layout` does.
+
+# Examples
+
+```rust
+use wyz::align;
+
+assert_eq!(align::align_up(13, 8), Some(16));
+assert_eq!(align::align_down(13, 8), 8);
+assert!(align::is_aligned(16, 8));
+assert_eq!(align::padding_needed_for(13, 8), 3);
+
+assert_eq!(align::align_up(usize::MAX, 2), None);
+```
+!*/
+
+/// Rounds `value` up to the next multiple of `align`.
+///
+/// ## Returns
+///
+/// `None` if the rounded value does not fit in a `usize`.
+///
+/// ## Panics
+///
+/// This panics if `align` is not a power of two.
+#[inline]
+pub fn align_up(value: usize, align: usize) -> Option<usize> {
+	value.checked_add(padding_needed_for(value, align))
+}
+
+/// Rounds `value` down to the previous multiple of `align`.
+///
+/// ## Panics
+///
+/// This panics if `align` is not a power of two.
+#[inline]
+pub fn align_down(value: usize, align: usize) -> usize {
+	value & !mask(align)
+}
+
+/// Tests whether `value` is a multiple of `align`.
+///
+/// ## Panics
+///
+/// This panics if `align` is not a power of two.
+#[inline]
+pub fn is_aligned(value: usize, align: usize) -> bool {
+	value & mask(align) == 0
+}
+
+/// Computes how much must be added to `value` to make it a multiple of
+/// `align`.
+///
+/// ## Panics
+///
+/// This panics if `align` is not a power of two.
+#[inline]
+pub fn padding_needed_for(value: usize, align: usize) -> usize {
+	value.wrapping_neg() & mask(align)
+}
+
+/// Produces the mask of the bits below `align`.
+#[inline]
+fn mask(align: usize) -> usize {
+	assert!(
+		align.is_power_of_two(),
+		"alignment {} is not a power of two",
+		align,
+	);
+	align - 1
+}
+
+/// Alignment arithmetic on addresses.
+///
+/// Pointers are moved with wrapping byte offsets, so they keep their
+/// provenance, but may need to be checked against the bounds of their
+/// allocation before they are dereferenced.
+///
+/// ## Examples
+///
+/// ```rust
+/// use wyz::align::AlignExt;
+///
+/// let buf = [0u8; 32];
+/// let ptr = buf.as_ptr().wrapping_add(1);
+/// let aligned = ptr.align_up(8).unwrap();
+/// assert!(aligned.is_aligned_at(8));
+/// assert_eq!(ptr.padding_needed_for(8), aligned as usize - ptr as usize);
+/// ```
+pub trait AlignExt: Copy {
+	/// Rounds up to the next multiple of `align`, or produces `None` if that
+	/// overflows the address space.
+	fn align_up(self, align: usize) -> Option<Self>;
+
+	/// Rounds down to the previous multiple of `align`.
+	fn align_down(self, align: usize) -> Self;
+
+	/// Tests whether `self` is a multiple of `align`.
+	fn is_aligned_at(self, align: usize) -> bool;
+
+	/// Computes how many bytes must be added to `self` to make it a multiple
+	/// of `align`.
+	fn padding_needed_for(self, align: usize) -> usize;
+}
+
+impl AlignExt for usize {
+	#[inline(always)]
+	fn align_up(self, align: usize) -> Option<Self> {
+		align_up(self, align)
+	}
+
+	#[inline(always)]
+	fn align_down(self, align: usize) -> Self {
+		align_down(self, align)
+	}
+
+	#[inline(always)]
+	fn is_aligned_at(self, align: usize) -> bool {
+		is_aligned(self, align)
+	}
+
+	#[inline(always)]
+	fn padding_needed_for(self, align: usize) -> usize {
+		padding_needed_for(self, align)
+	}
+}
+
+macro_rules! align_ptr {
+	($($ptr:ty => $bytes:ty),+ $(,)?) => { $(
+		impl<T> AlignExt for $ptr {
+			#[inline]
+			fn align_up(self, align: usize) -> Option<Self> {
+				let pad = padding_needed_for(self as usize, align);
+				(self as usize).checked_add(pad)?;
+				Some((self as $bytes).wrapping_add(pad) as Self)
+			}
+
+			#[inline]
+			fn align_down(self, align: usize) -> Self {
+				let excess = self as usize & mask(align);
+				(self as $bytes).wrapping_sub(excess) as Self
+			}
+
+			#[inline(always)]
+			fn is_aligned_at(self, align: usize) -> bool {
+				is_aligned(self as usize, align)
+			}
+
+			#[inline(always)]
+			fn padding_needed_for(self, align: usize) -> usize {
+				padding_needed_for(self as usize, align)
+			}
+		}
+	)+ };
+}
+
+align_ptr!(*const T => *const u8, *mut T => *mut u8);
+
+#[cfg(test)]
+mod tests {
+	use core::ptr;
+
+	use super::*;
+
+	#[test]
+	fn values() {
+		assert_eq!(align_up(0, 4), Some(0));
+		assert_eq!(align_up(1, 1), Some(1));
+		assert_eq!(align_up(usize::MAX - 7, 8), Some(usize::MAX - 7));
+		assert_eq!(align_up(usize::MAX - 6, 8), None);
+		assert_eq!(align_down(usize::MAX, 8), usize::MAX - 7);
+		assert!(is_aligned(0, 4096));
+		assert!(!is_aligned(2, 4));
+		assert_eq!(padding_needed_for(0, 8), 0);
+		assert_eq!(padding_needed_for(usize::MAX, 8), 1);
+		assert_eq!(9usize.align_up(4), Some(12));
+		assert_eq!(9usize.align_down(4), 8);
+	}
+
+	#[test]
+	#[should_panic(expected = "alignment 6 is not a power of two")]
+	fn non_power() {
+		align_down(12, 6);
+	}
+
+	#[test]
+	fn pointers() {
+		let mut buf = [0u64; 4];
+		let base = buf.as_mut_ptr();
+		let odd = (base as *mut u8).wrapping_add(3);
+		assert!(!odd.is_aligned_at(8));
+		assert_eq!(odd.padding_needed_for(8), 5);
+		assert_eq!(odd.align_up(8), Some(base.wrapping_add(1) as *mut u8));
+		assert_eq!(odd.align_down(8), base as *mut u8);
+
+		let end = usize::MAX as *const u16;
+		assert!(end.align_up(2).is_none());
+		assert!(ptr::null::<u8>().is_aligned_at(4096));
+	}
+}
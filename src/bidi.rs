@@ -1,6 +1,9 @@
-//! A bidirectional iterator that only checks its direction once.
+//! Bidirectional iterators, and adapters that traverse both ends of a sequence.
 
-use core::iter::FusedIterator;
+use core::iter::{
+	Enumerate,
+	FusedIterator,
+};
 
 /** An iterator that conditionally reverses itself upon creation.
 
@@ -8,8 +11,11 @@ This acts as a conditional `.rev()` adapter: it reverses the direction of
 iteration, swapping `.next()` and `.next_back()`, but only if the provided
 condition is true. If the condition is false, then iteration proceeds normally.
 
-The condition is only evaluated when the adapter is constructed, and all calls
-to drive the iterator are branchless.
+The condition is evaluated when the adapter is constructed, or when the
+direction is changed with `.set_reversed()` or `.toggle()`, and all calls to
+drive the iterator are branchless. Internal iteration (`.fold()` and
+`.rfold()`, which power most of the consuming adapters) checks the direction
+once and then forwards to the inner iterator’s own implementation.
 
 ## Usage
 
@@ -44,6 +50,8 @@ where I: DoubleEndedIterator
 {
 	/// The iterator being governed.
 	inner: I,
+	/// Whether the direction of iteration is reversed.
+	reversed: bool,
 	/// A pointer to either `I::next` or `I::next_back`.
 	next: fn(&mut I) -> Option<<I as Iterator>::Item>,
 	/// A pointer to either `I::next_back` or `I::next`.
@@ -66,26 +74,78 @@ where I: DoubleEndedIterator
 	/// - `cond`: determines whether iteration proceeds ordinarily or reversed
 	pub fn new<II>(iter: II, cond: bool) -> Self
 	where II: IntoIterator<IntoIter = I> {
-		let inner = iter.into_iter();
+		let mut this = Self {
+			inner: iter.into_iter(),
+			reversed: false,
+			next: <I as Iterator>::next,
+			next_back: <I as DoubleEndedIterator>::next_back,
+			nth: <I as Iterator>::nth,
+			nth_back: <I as DoubleEndedIterator>::nth_back,
+		};
+		this.set_reversed(cond);
+		this
+	}
+
+	/// Tests whether the direction of iteration is currently reversed.
+	#[inline]
+	pub fn is_reversed(&self) -> bool {
+		self.reversed
+	}
+
+	/// Sets the direction of iteration.
+	///
+	/// Iteration resumes from wherever the underlying iterator has been
+	/// consumed to: items already produced from either end are not revisited.
+	///
+	/// ## Parameters
+	///
+	/// - `cond`: when true, `.next()` draws from the back of the underlying
+	///   iterator; when false, it draws from the front.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::BidiIterator;
+	///
+	/// let mut iter = (0 .. 6).bidi(false);
+	/// assert_eq!(iter.next(), Some(0));
+	/// iter.set_reversed(true);
+	/// assert_eq!(iter.next(), Some(5));
+	/// ```
+	pub fn set_reversed(&mut self, cond: bool) {
+		self.reversed = cond;
 		if cond {
-			Self {
-				inner,
-				next: <I as DoubleEndedIterator>::next_back,
-				next_back: <I as Iterator>::next,
-				nth: <I as DoubleEndedIterator>::nth_back,
-				nth_back: <I as Iterator>::nth,
-			}
+			self.next = <I as DoubleEndedIterator>::next_back;
+			self.next_back = <I as Iterator>::next;
+			self.nth = <I as DoubleEndedIterator>::nth_back;
+			self.nth_back = <I as Iterator>::nth;
 		}
 		else {
-			Self {
-				inner,
-				next: <I as Iterator>::next,
-				next_back: <I as DoubleEndedIterator>::next_back,
-				nth: <I as Iterator>::nth,
-				nth_back: <I as DoubleEndedIterator>::nth_back,
-			}
+			self.next = <I as Iterator>::next;
+			self.next_back = <I as DoubleEndedIterator>::next_back;
+			self.nth = <I as Iterator>::nth;
+			self.nth_back = <I as DoubleEndedIterator>::nth_back;
 		}
 	}
+
+	/// Reverses the current direction of iteration.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::BidiIterator;
+	///
+	/// let mut iter = (0 .. 6).bidi(false);
+	/// assert_eq!(iter.next(), Some(0));
+	/// iter.toggle();
+	/// assert_eq!(iter.next(), Some(5));
+	/// iter.toggle();
+	/// assert_eq!(iter.next(), Some(1));
+	/// ```
+	#[inline]
+	pub fn toggle(&mut self) {
+		self.set_reversed(!self.reversed);
+	}
 }
 
 impl<I> Iterator for Bidi<I>
@@ -120,6 +180,17 @@ where I: DoubleEndedIterator
 	fn last(mut self) -> Option<Self::Item> {
 		self.next_back()
 	}
+
+	#[inline]
+	fn fold<B, F>(self, init: B, func: F) -> B
+	where F: FnMut(B, Self::Item) -> B {
+		if self.reversed {
+			self.inner.rfold(init, func)
+		}
+		else {
+			self.inner.fold(init, func)
+		}
+	}
 }
 
 impl<I> DoubleEndedIterator for Bidi<I>
@@ -134,6 +205,17 @@ where I: DoubleEndedIterator
 	fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
 		(self.nth_back)(&mut self.inner, n)
 	}
+
+	#[inline]
+	fn rfold<B, F>(self, init: B, func: F) -> B
+	where F: FnMut(B, Self::Item) -> B {
+		if self.reversed {
+			self.inner.fold(init, func)
+		}
+		else {
+			self.inner.rfold(init, func)
+		}
+	}
 }
 
 impl<I> ExactSizeIterator for Bidi<I>
@@ -150,7 +232,499 @@ impl<I> FusedIterator for Bidi<I> where I: DoubleEndedIterator + FusedIterator
 {
 }
 
-/// Extension trait that provides `.bidi()` for all double-ended iterators.
+/** An iterator that alternates between the front and back of a sequence.
+
+This yields the first item, then the last, then the second, then the
+second-to-last, and so on, until the two ends meet in the middle. Each item of
+the underlying iterator is produced exactly once.
+
+## Usage
+
+This is constructed by calling `.converge()` on any double-ended iterator, after
+importing `wyz::BidiIterator`.
+
+## Examples
+
+```rust
+use wyz::BidiIterator;
+
+let order = (0 .. 5).converge().collect::<Vec<_>>();
+assert_eq!(order, [0, 4, 1, 3, 2]);
+```
+**/
+pub struct Converge<I>
+where I: DoubleEndedIterator
+{
+	/// The iterator being governed.
+	inner: I,
+	/// Whether the next item is drawn from the back.
+	back: bool,
+}
+
+impl<I> Converge<I>
+where I: DoubleEndedIterator
+{
+	/// Applies the `Converge` adapter to a double-ended iterator.
+	///
+	/// The first item is drawn from the front.
+	pub fn new<II>(iter: II) -> Self
+	where II: IntoIterator<IntoIter = I> {
+		Self {
+			inner: iter.into_iter(),
+			back: false,
+		}
+	}
+}
+
+impl<I> Iterator for Converge<I>
+where I: DoubleEndedIterator
+{
+	type Item = <I as Iterator>::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let out = if self.back {
+			self.inner.next_back()
+		}
+		else {
+			self.inner.next()
+		};
+		self.back = !self.back;
+		out
+	}
+
+	#[inline]
+	#[cfg(not(tarpaulin_include))]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+
+	#[inline]
+	#[cfg(not(tarpaulin_include))]
+	fn count(self) -> usize {
+		self.inner.count()
+	}
+}
+
+impl<I> ExactSizeIterator for Converge<I>
+where I: DoubleEndedIterator + ExactSizeIterator
+{
+	#[inline]
+	#[cfg(not(tarpaulin_include))]
+	fn len(&self) -> usize {
+		self.inner.len()
+	}
+}
+
+impl<I> FusedIterator for Converge<I> where I: DoubleEndedIterator + FusedIterator
+{
+}
+
+/** An iterator that walks a sequence forward, then backward, repeatedly.
+
+This produces a triangle wave over the underlying sequence: it yields every
+item from front to back, then every item from back to front, and so on, forever.
+The endpoints are not repeated at each turn, so `[0, 1, 2]` produces
+`0, 1, 2, 1, 0, 1, 2, 1, 0, …`.
+
+A sequence of a single item yields that item forever, and an empty sequence
+yields nothing.
+
+## Usage
+
+This is constructed by calling `.ping_pong()` on any double-ended iterator that
+can be cloned, after importing `wyz::BidiIterator`. Each pass clones the
+original iterator, so the items themselves do not need to be `Clone`.
+
+## Examples
+
+```rust
+use wyz::BidiIterator;
+
+let wave = (0 .. 3).ping_pong().take(9).collect::<Vec<_>>();
+assert_eq!(wave, [0, 1, 2, 1, 0, 1, 2, 1, 0]);
+```
+**/
+pub struct PingPong<I>
+where I: DoubleEndedIterator + Clone
+{
+	/// The original iterator, from which each pass is cloned.
+	orig: I,
+	/// The pass currently being walked.
+	pass: I,
+	/// Whether the current pass walks from back to front.
+	reversed: bool,
+}
+
+impl<I> PingPong<I>
+where I: DoubleEndedIterator + Clone
+{
+	/// Applies the `PingPong` adapter to a double-ended iterator.
+	///
+	/// The first pass walks from front to back.
+	pub fn new<II>(iter: II) -> Self
+	where II: IntoIterator<IntoIter = I> {
+		let orig = iter.into_iter();
+		Self {
+			pass: orig.clone(),
+			orig,
+			reversed: false,
+		}
+	}
+
+	/// Draws the next item from the current pass.
+	#[inline]
+	fn step(&mut self) -> Option<<I as Iterator>::Item> {
+		if self.reversed {
+			self.pass.next_back()
+		}
+		else {
+			self.pass.next()
+		}
+	}
+}
+
+impl<I> Iterator for PingPong<I>
+where I: DoubleEndedIterator + Clone
+{
+	type Item = <I as Iterator>::Item;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if let Some(item) = self.step() {
+			return Some(item);
+		}
+		//  Turn around, and skip the endpoint that the last pass produced.
+		//  When there is nothing past it, the sequence has one item, which is
+		//  produced again.
+		self.reversed = !self.reversed;
+		self.pass = self.orig.clone();
+		let endpoint = self.step();
+		self.step().or(endpoint)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		match self.orig.size_hint() {
+			(_, Some(0)) => (0, Some(0)),
+			(0, _) => (0, None),
+			_ => (usize::MAX, None),
+		}
+	}
+}
+
+impl<I> FusedIterator for PingPong<I> where I: DoubleEndedIterator + Clone + FusedIterator
+{
+}
+
+/** A double-ended iterator that can look ahead at both of its ends.
+
+The standard library’s `Peekable` can only inspect the next item from the front.
+This adapter buffers at most one item at each end, so that `.peek()` and
+`.peek_back()` can inspect the next item from either direction without
+consuming it.
+
+## Usage
+
+This is constructed by calling `.peekable2()` on any double-ended iterator,
+after importing `wyz::BidiIterator`.
+
+## Examples
+
+This trims spaces from both ends of a character stream:
+
+```rust
+use wyz::BidiIterator;
+
+let mut iter = "  text ".chars().peekable2();
+while iter.peek() == Some(&' ') {
+  iter.next();
+}
+while iter.peek_back() == Some(&' ') {
+  iter.next_back();
+}
+assert_eq!(iter.collect::<String>(), "text");
+```
+**/
+pub struct Peekable2<I>
+where I: DoubleEndedIterator
+{
+	/// The iterator being governed.
+	inner: I,
+	/// An item drawn from the front of `inner` by `.peek()`.
+	front: Option<<I as Iterator>::Item>,
+	/// An item drawn from the back of `inner` by `.peek_back()`.
+	back: Option<<I as Iterator>::Item>,
+}
+
+impl<I> Peekable2<I>
+where I: DoubleEndedIterator
+{
+	/// Applies the `Peekable2` adapter to a double-ended iterator.
+	pub fn new<II>(iter: II) -> Self
+	where II: IntoIterator<IntoIter = I> {
+		Self {
+			inner: iter.into_iter(),
+			front: None,
+			back: None,
+		}
+	}
+
+	/// Views the item that the next call to `.next()` will produce.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::BidiIterator;
+	///
+	/// let mut iter = (0 .. 3).peekable2();
+	/// assert_eq!(iter.peek(), Some(&0));
+	/// assert_eq!(iter.next(), Some(0));
+	/// ```
+	pub fn peek(&mut self) -> Option<&<I as Iterator>::Item> {
+		if self.front.is_none() {
+			self.front = self.inner.next().or_else(|| self.back.take());
+		}
+		self.front.as_ref()
+	}
+
+	/// Views the item that the next call to `.next_back()` will produce.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::BidiIterator;
+	///
+	/// let mut iter = (0 .. 3).peekable2();
+	/// assert_eq!(iter.peek_back(), Some(&2));
+	/// assert_eq!(iter.next_back(), Some(2));
+	/// ```
+	pub fn peek_back(&mut self) -> Option<&<I as Iterator>::Item> {
+		if self.back.is_none() {
+			self.back = self.inner.next_back().or_else(|| self.front.take());
+		}
+		self.back.as_ref()
+	}
+
+	/// Counts the items held in the peek buffers.
+	#[inline]
+	fn buffered(&self) -> usize {
+		self.front.is_some() as usize + self.back.is_some() as usize
+	}
+}
+
+impl<I> Iterator for Peekable2<I>
+where I: DoubleEndedIterator
+{
+	type Item = <I as Iterator>::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.front
+			.take()
+			.or_else(|| self.inner.next())
+			.or_else(|| self.back.take())
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let buffered = self.buffered();
+		let (lo, hi) = self.inner.size_hint();
+		(
+			lo.saturating_add(buffered),
+			hi.and_then(|hi| hi.checked_add(buffered)),
+		)
+	}
+}
+
+impl<I> DoubleEndedIterator for Peekable2<I>
+where I: DoubleEndedIterator
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.back
+			.take()
+			.or_else(|| self.inner.next_back())
+			.or_else(|| self.front.take())
+	}
+}
+
+impl<I> ExactSizeIterator for Peekable2<I> where I: DoubleEndedIterator + ExactSizeIterator
+{
+}
+
+impl<I> FusedIterator for Peekable2<I> where I: DoubleEndedIterator + FusedIterator
+{
+}
+
+/** A cursor over a slice that can change direction and report its position.
+
+The cursor sits *between* elements, like a text caret: its position ranges from
+`0` (before the first element) to `len` (after the last). Moving forward
+produces the element after the cursor; moving in reverse produces the element
+before it. Switching direction therefore produces the element that was just
+visited again, exactly as a caret moving back over what it has just passed.
+
+Because turning the cursor around, or moving it, can make it produce elements
+again after it has returned `None`, it does not implement `FusedIterator`.
+
+Unlike `Bidi`, which only sees an opaque iterator, this can tell its user where
+it is and what lies on either side of it, which slice-scanning parsers need.
+
+## Examples
+
+```rust
+use wyz::bidi::BidiSlice;
+
+let data = [1, 2, 3, 4, 5];
+let mut cursor = BidiSlice::new(&data, false);
+assert_eq!(cursor.next(), Some(&1));
+assert_eq!(cursor.next(), Some(&2));
+assert_eq!(cursor.position(), 2);
+assert_eq!(cursor.remaining(), &[3, 4, 5]);
+
+cursor.toggle();
+assert_eq!(cursor.remaining(), &[1, 2]);
+assert_eq!(cursor.next(), Some(&2));
+assert_eq!(cursor.position(), 1);
+```
+**/
+#[derive(Clone, Copy, Debug)]
+pub struct BidiSlice<'a, T> {
+	/// The slice being traversed.
+	slice: &'a [T],
+	/// The index of the element immediately after the cursor.
+	pos: usize,
+	/// Whether the cursor moves toward the front of the slice.
+	reversed: bool,
+}
+
+impl<'a, T> BidiSlice<'a, T> {
+	/// Creates a cursor over a slice.
+	///
+	/// ## Parameters
+	///
+	/// - `slice`: the slice to traverse
+	/// - `cond`: when false, the cursor starts at the front and moves toward
+	///   the back; when true, it starts at the back and moves toward the front
+	pub fn new(slice: &'a [T], cond: bool) -> Self {
+		Self {
+			slice,
+			pos: if cond { slice.len() } else { 0 },
+			reversed: cond,
+		}
+	}
+
+	/// Views the entire underlying slice.
+	#[inline]
+	pub fn as_slice(&self) -> &'a [T] {
+		self.slice
+	}
+
+	/// Gets the cursor’s position, between `0` and the slice length.
+	#[inline]
+	pub fn position(&self) -> usize {
+		self.pos
+	}
+
+	/// Moves the cursor to a new position.
+	///
+	/// ## Panics
+	///
+	/// This panics if `pos` is greater than the length of the slice.
+	pub fn set_position(&mut self, pos: usize) {
+		assert!(
+			pos <= self.slice.len(),
+			"cursor position {} is out of bounds for length {}",
+			pos,
+			self.slice.len(),
+		);
+		self.pos = pos;
+	}
+
+	/// Tests whether the cursor is moving toward the front of the slice.
+	#[inline]
+	pub fn is_reversed(&self) -> bool {
+		self.reversed
+	}
+
+	/// Sets the direction of travel.
+	#[inline]
+	pub fn set_reversed(&mut self, cond: bool) {
+		self.reversed = cond;
+	}
+
+	/// Reverses the direction of travel.
+	#[inline]
+	pub fn toggle(&mut self) {
+		self.reversed = !self.reversed;
+	}
+
+	/// Views the elements before the cursor.
+	#[inline]
+	pub fn before(&self) -> &'a [T] {
+		&self.slice[.. self.pos]
+	}
+
+	/// Views the elements after the cursor.
+	#[inline]
+	pub fn after(&self) -> &'a [T] {
+		&self.slice[self.pos ..]
+	}
+
+	/// Views the elements that the cursor has yet to reach in its current
+	/// direction of travel.
+	///
+	/// This is `.after()` when moving forward, and `.before()` when moving in
+	/// reverse. The sub-slice is always in the slice’s own order.
+	#[inline]
+	pub fn remaining(&self) -> &'a [T] {
+		if self.reversed {
+			self.before()
+		}
+		else {
+			self.after()
+		}
+	}
+
+	/// Views the element that the next call to `.next()` will produce.
+	#[inline]
+	pub fn peek(&self) -> Option<&'a T> {
+		if self.reversed {
+			self.before().last()
+		}
+		else {
+			self.after().first()
+		}
+	}
+}
+
+impl<'a, T> Iterator for BidiSlice<'a, T> {
+	type Item = &'a T;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let out = self.peek()?;
+		if self.reversed {
+			self.pos -= 1;
+		}
+		else {
+			self.pos += 1;
+		}
+		Some(out)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.remaining().len();
+		(len, Some(len))
+	}
+}
+
+impl<T> ExactSizeIterator for BidiSlice<'_, T> {
+}
+
+/// Extension trait that provides `.bidi()` and related adapters for all
+/// double-ended iterators.
 pub trait BidiIterator
 where
 	Self: Sized + IntoIterator,
@@ -179,6 +753,85 @@ where
 	fn bidi(self, cond: bool) -> Bidi<Self::IntoIter> {
 		Bidi::new(self, cond)
 	}
+
+	/// Conditionally reverses the direction of iteration, pairing each item
+	/// with its index in the *forward* order.
+	///
+	/// `.bidi(true).enumerate()` counts positions in the order that items are
+	/// produced, so the last item is numbered `0`. This adapter instead
+	/// numbers each item by where it sits in the original sequence, no matter
+	/// which end it is drawn from, so that reverse traversals do not need to
+	/// compute `len - 1 - i` by hand.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::BidiIterator;
+	///
+	/// let data = ['a', 'b', 'c'];
+	/// let mut iter = data.iter().copied().bidi_enumerate(true);
+	/// assert_eq!(iter.next(), Some((2, 'c')));
+	/// assert_eq!(iter.next(), Some((1, 'b')));
+	/// assert_eq!(iter.next_back(), Some((0, 'a')));
+	/// ```
+	fn bidi_enumerate(self, cond: bool) -> Bidi<Enumerate<Self::IntoIter>>
+	where Self::IntoIter: ExactSizeIterator {
+		Bidi::new(self.into_iter().enumerate(), cond)
+	}
+
+	/// Alternates between drawing items from the front and the back, until the
+	/// two ends meet.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::BidiIterator;
+	///
+	/// let mut iter = "abcdef".chars().converge();
+	/// assert_eq!(iter.next(), Some('a'));
+	/// assert_eq!(iter.next(), Some('f'));
+	/// assert_eq!(iter.next(), Some('b'));
+	/// assert_eq!(iter.next(), Some('e'));
+	/// ```
+	fn converge(self) -> Converge<Self::IntoIter> {
+		Converge::new(self)
+	}
+
+	/// Walks the sequence forward, then backward, repeatedly, without
+	/// repeating the endpoints at each turn.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::BidiIterator;
+	///
+	/// let mut iter = [1, 2].iter().ping_pong();
+	/// assert_eq!(iter.next(), Some(&1));
+	/// assert_eq!(iter.next(), Some(&2));
+	/// assert_eq!(iter.next(), Some(&1));
+	/// assert_eq!(iter.next(), Some(&2));
+	/// ```
+	fn ping_pong(self) -> PingPong<Self::IntoIter>
+	where Self::IntoIter: Clone {
+		PingPong::new(self)
+	}
+
+	/// Allows looking ahead at the next item from either end, without
+	/// consuming it.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::BidiIterator;
+	///
+	/// let mut iter = [1, 2, 3].iter().copied().peekable2();
+	/// assert_eq!(iter.peek(), Some(&1));
+	/// assert_eq!(iter.peek_back(), Some(&3));
+	/// assert_eq!(iter.len(), 3);
+	/// ```
+	fn peekable2(self) -> Peekable2<Self::IntoIter> {
+		Peekable2::new(self)
+	}
 }
 
 impl<I> BidiIterator for I
@@ -213,4 +866,145 @@ mod tests {
 		assert_eq!(iter.nth_back(1), Some(2));
 		assert!(iter.next().is_none());
 	}
+
+	#[test]
+	fn switching() {
+		let mut iter = (0 .. 8).bidi(false);
+		assert!(!iter.is_reversed());
+		assert_eq!(iter.next(), Some(0));
+
+		iter.toggle();
+		assert!(iter.is_reversed());
+		assert_eq!(iter.next(), Some(7));
+		assert_eq!(iter.next_back(), Some(1));
+		assert_eq!(iter.nth(1), Some(5));
+
+		iter.set_reversed(false);
+		assert_eq!(iter.next(), Some(2));
+		assert_eq!(iter.next_back(), Some(4));
+		assert_eq!(iter.next(), Some(3));
+		assert!(iter.next().is_none());
+	}
+
+	#[test]
+	fn enumerating() {
+		let mut iter = (10 .. 14).bidi_enumerate(true);
+		assert_eq!(iter.next(), Some((3, 13)));
+		assert_eq!(iter.next_back(), Some((0, 10)));
+		iter.toggle();
+		assert_eq!(iter.next(), Some((1, 11)));
+		assert_eq!(iter.next(), Some((2, 12)));
+		assert!(iter.next().is_none());
+
+		let mut iter = (10 .. 14).bidi_enumerate(false);
+		assert_eq!(iter.next(), Some((0, 10)));
+		assert_eq!(iter.next_back(), Some((3, 13)));
+	}
+
+	#[test]
+	fn converging() {
+		let mut iter = (0 .. 6).converge();
+		assert_eq!(iter.len(), 6);
+		assert_eq!(iter.next(), Some(0));
+		assert_eq!(iter.next(), Some(5));
+		assert_eq!(iter.next(), Some(1));
+		assert_eq!(iter.len(), 3);
+		assert_eq!(iter.next(), Some(4));
+		assert_eq!(iter.next(), Some(2));
+		assert_eq!(iter.next(), Some(3));
+		assert!(iter.next().is_none());
+		assert!(iter.next().is_none());
+
+		assert!((0 .. 0).converge().next().is_none());
+		assert_eq!((0 .. 1).converge().count(), 1);
+	}
+
+	#[test]
+	fn ping_pong() {
+		let wave = (0 .. 4).ping_pong().take(13);
+		assert!(
+			wave.eq([0, 1, 2, 3, 2, 1, 0, 1, 2, 3, 2, 1, 0].iter().copied())
+		);
+
+		let mut iter = (5 .. 6).ping_pong();
+		assert_eq!(iter.size_hint(), (usize::MAX, None));
+		assert!(iter.by_ref().take(4).all(|n| n == 5));
+
+		let mut iter = (0 .. 0).ping_pong();
+		assert_eq!(iter.size_hint(), (0, Some(0)));
+		assert!(iter.next().is_none());
+		assert!(iter.next().is_none());
+	}
+
+	#[test]
+	fn peeking() {
+		let mut iter = (0 .. 3).peekable2();
+		assert_eq!(iter.peek(), Some(&0));
+		assert_eq!(iter.peek_back(), Some(&2));
+		assert_eq!(iter.len(), 3);
+		assert_eq!(iter.next_back(), Some(2));
+		assert_eq!(iter.peek_back(), Some(&1));
+		assert_eq!(iter.peek(), Some(&0));
+		assert_eq!(iter.next(), Some(0));
+
+		//  The last item is held in the back buffer, and `.peek()` finds it.
+		assert_eq!(iter.peek(), Some(&1));
+		assert_eq!(iter.peek_back(), Some(&1));
+		assert_eq!(iter.len(), 1);
+		assert_eq!(iter.next_back(), Some(1));
+		assert!(iter.peek().is_none());
+		assert!(iter.peek_back().is_none());
+		assert!(iter.next().is_none());
+	}
+
+	#[test]
+	fn slice_cursor() {
+		let data = [0, 1, 2, 3, 4];
+		let mut cursor = BidiSlice::new(&data, true);
+		assert_eq!(cursor.position(), 5);
+		assert_eq!(cursor.len(), 5);
+		assert_eq!(cursor.next(), Some(&4));
+		assert_eq!(cursor.next(), Some(&3));
+		assert_eq!(cursor.before(), &[0, 1, 2]);
+		assert_eq!(cursor.after(), &[3, 4]);
+
+		cursor.set_reversed(false);
+		assert_eq!(cursor.len(), 2);
+		assert_eq!(cursor.peek(), Some(&3));
+		assert_eq!(cursor.next(), Some(&3));
+		assert_eq!(cursor.next(), Some(&4));
+		assert!(cursor.next().is_none());
+		assert!(cursor.remaining().is_empty());
+
+		cursor.set_position(1);
+		cursor.toggle();
+		assert_eq!(cursor.next(), Some(&0));
+		assert!(cursor.next().is_none());
+		assert_eq!(cursor.as_slice(), &data);
+	}
+
+	#[test]
+	#[should_panic]
+	fn slice_cursor_bounds() {
+		BidiSlice::new(&[0u8; 3], false).set_position(4);
+	}
+
+	#[test]
+	fn folding() {
+		let push = |mut acc: [u8; 4], n: u8| {
+			acc.rotate_left(1);
+			acc[3] = n;
+			acc
+		};
+
+		assert_eq!((0 .. 4).bidi(false).fold([0; 4], push), [0, 1, 2, 3]);
+		assert_eq!((0 .. 4).bidi(true).fold([0; 4], push), [3, 2, 1, 0]);
+		assert_eq!((0 .. 4).bidi(false).rfold([0; 4], push), [3, 2, 1, 0]);
+		assert_eq!((0 .. 4).bidi(true).rfold([0; 4], push), [0, 1, 2, 3]);
+
+		let iter = (0 .. 4).bidi(true);
+		assert_eq!(iter.size_hint(), (4, Some(4)));
+		assert_eq!(iter.len(), 4);
+		assert_eq!(iter.last(), Some(0));
+	}
 }
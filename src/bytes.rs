@@ -0,0 +1,128 @@
+/*! Byte views of plain-old-data values
+
+Serialization, checksumming, and hashing code frequently needs to look at the
+raw bytes of a value. This module provides the [`BytesOf`] trait, which views a
+value as a byte slice, for exactly the types where doing so is sound: the
+primitive integers and floats, and arrays and slices of them.
+
+These types have no padding bytes and no invalid bit patterns, so their bytes
+can be both read and written freely. The trait is sealed, so that it cannot be
+implemented for types that do not uphold these requirements.
+
+The bytes are in the target’s native byte order.
+!*/
+
+use core::{
+	mem,
+	slice,
+};
+
+use crate::endian::{
+	BigEndian,
+	Endian,
+	LittleEndian,
+};
+
+/// Views plain-old-data values as slices of bytes.
+pub trait BytesOf: seal::Sealed {
+	/// Views the value as its underlying bytes.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::bytes::BytesOf;
+	///
+	/// assert_eq!(0x1234u16.to_ne_bytes(), 0x1234u16.as_bytes());
+	///
+	/// let pair = [1u8, 2];
+	/// assert_eq!(pair.as_bytes(), &[1, 2]);
+	/// ```
+	#[inline]
+	fn as_bytes(&self) -> &[u8] {
+		//  SAFETY: implementors have no padding, so every byte is initialized.
+		unsafe {
+			slice::from_raw_parts(
+				self as *const Self as *const u8,
+				mem::size_of_val(self),
+			)
+		}
+	}
+
+	/// Views the value as its underlying bytes, which may be modified.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::bytes::BytesOf;
+	///
+	/// let mut words = [0u16; 2];
+	/// words.as_bytes_mut().copy_from_slice(&[0xFF; 4]);
+	/// assert_eq!(words, [!0; 2]);
+	/// ```
+	#[inline]
+	fn as_bytes_mut(&mut self) -> &mut [u8] {
+		//  SAFETY: implementors have no padding, and every bit pattern is a
+		//  valid value, so any bytes written produce a valid value.
+		unsafe {
+			slice::from_raw_parts_mut(
+				self as *mut Self as *mut u8,
+				mem::size_of_val(self),
+			)
+		}
+	}
+}
+
+macro_rules! bytes_of {
+	($($t:ty),+ $(,)?) => { $(
+		impl BytesOf for $t {}
+	)+ };
+}
+
+bytes_of!(
+	u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64,
+);
+
+impl<T, const N: usize> BytesOf for [T; N] where T: BytesOf
+{
+}
+
+impl<T> BytesOf for [T] where T: BytesOf
+{
+}
+
+crate::sealed! {
+	mod seal;
+	u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64,
+	impl<T, const N: usize> [T; N],
+	impl<T> [T],
+	impl<T: Endian> BigEndian<T>,
+	impl<T: Endian> LittleEndian<T>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn views() {
+		assert_eq!(5u8.as_bytes(), &[5]);
+		assert_eq!(0x0102_0304u32.as_bytes(), &0x0102_0304u32.to_ne_bytes());
+		assert_eq!(1.5f64.as_bytes(), &1.5f64.to_ne_bytes());
+		assert_eq!([[1u8, 2], [3, 4]].as_bytes(), &[1, 2, 3, 4]);
+
+		let words: &[u16] = &[1, 2, 3];
+		assert_eq!(words.as_bytes().len(), 6);
+		assert!(<[u32]>::as_bytes(&[]).is_empty());
+	}
+
+	#[test]
+	fn writes() {
+		let mut num = 0i32;
+		num.as_bytes_mut().copy_from_slice(&(-2i32).to_ne_bytes());
+		assert_eq!(num, -2);
+
+		let mut words = [0u16; 3];
+		words[1 ..].as_bytes_mut().fill(0xAB);
+		assert_eq!(words, [0, 0xABAB, 0xABAB]);
+	}
+}
@@ -0,0 +1,223 @@
+/*! Checked arithmetic with errors
+
+The standard library’s `checked_*` methods report failure as `None`, which
+cannot be propagated with `?` from a function that returns `Result`, and which
+does not describe what went wrong. The [`CheckedExt`] trait provides `_err`
+variants of them that produce an [`ArithmeticError`] naming the operation and
+its operands.
+
+# Examples
+
+```rust
+use wyz::checked::{ArithmeticError, CheckedExt};
+
+fn area(width: u16, height: u16) -> Result<u16, ArithmeticError<u16>> {
+ width.checked_mul_err(height)
+}
+
+let err = area(300, 300).unwrap_err();
+assert_eq!(err.to_string(), "arithmetic overflow in `300 * 300`");
+```
+!*/
+
+use core::fmt::{
+	self,
+	Debug,
+	Display,
+	Formatter,
+};
+
+/// Checked arithmetic that reports failures as errors.
+pub trait CheckedExt: seal::Sealed + Copy + Default + Eq + Sized {
+	/// Adds, or reports overflow.
+	fn checked_add_err(self, rhs: Self) -> Result<Self, ArithmeticError<Self>>;
+
+	/// Subtracts, or reports overflow.
+	fn checked_sub_err(self, rhs: Self) -> Result<Self, ArithmeticError<Self>>;
+
+	/// Multiplies, or reports overflow.
+	fn checked_mul_err(self, rhs: Self) -> Result<Self, ArithmeticError<Self>>;
+
+	/// Divides, or reports division by zero or overflow.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::checked::CheckedExt;
+	///
+	/// assert_eq!(7i8.checked_div_err(2), Ok(3));
+	/// assert_eq!(
+	///  7i8.checked_div_err(0).unwrap_err().to_string(),
+	///  "division by zero in `7 / 0`",
+	/// );
+	/// ```
+	fn checked_div_err(self, rhs: Self) -> Result<Self, ArithmeticError<Self>>;
+
+	/// Takes the remainder, or reports division by zero or overflow.
+	fn checked_rem_err(self, rhs: Self) -> Result<Self, ArithmeticError<Self>>;
+}
+
+/// An arithmetic operation.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Operation {
+	/// `+`
+	Add,
+	/// `-`
+	Sub,
+	/// `*`
+	Mul,
+	/// `/`
+	Div,
+	/// `%`
+	Rem,
+}
+
+impl Operation {
+	/// Gets the operator symbol.
+	#[inline]
+	pub fn symbol(self) -> &'static str {
+		match self {
+			Self::Add => "+",
+			Self::Sub => "-",
+			Self::Mul => "*",
+			Self::Div => "/",
+			Self::Rem => "%",
+		}
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl Display for Operation {
+	#[inline(always)]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str(self.symbol())
+	}
+}
+
+/// Describes a failed arithmetic operation.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ArithmeticError<T> {
+	/// The operation that failed.
+	pub op: Operation,
+	/// The left-hand operand.
+	pub lhs: T,
+	/// The right-hand operand.
+	pub rhs: T,
+}
+
+impl<T> ArithmeticError<T>
+where T: CheckedExt
+{
+	/// Tests whether the operation failed because it divided by zero, rather
+	/// than because it overflowed.
+	#[inline]
+	pub fn is_division_by_zero(&self) -> bool {
+		//  Every implementor is an integer, whose default value is zero.
+		matches!(self.op, Operation::Div | Operation::Rem)
+			&& self.rhs == T::default()
+	}
+}
+
+impl<T> Display for ArithmeticError<T>
+where T: CheckedExt + Display
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		let kind = if self.is_division_by_zero() {
+			"division by zero"
+		}
+		else {
+			"arithmetic overflow"
+		};
+		write!(fmt, "{} in `{} {} {}`", kind, self.lhs, self.op, self.rhs)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for ArithmeticError<T> where T: CheckedExt + Debug + Display
+{
+}
+
+macro_rules! checked_op {
+	($($name:ident => $method:ident, $op:ident);+ $(;)?) => { $(
+		#[inline]
+		fn $name(self, rhs: Self) -> Result<Self, ArithmeticError<Self>> {
+			self.$method(rhs).ok_or(ArithmeticError {
+				op: Operation::$op,
+				lhs: self,
+				rhs,
+			})
+		}
+	)+ };
+}
+
+macro_rules! checked {
+	($($t:ty),+ $(,)?) => { $(
+		impl CheckedExt for $t {
+			checked_op! {
+				checked_add_err => checked_add, Add;
+				checked_sub_err => checked_sub, Sub;
+				checked_mul_err => checked_mul, Mul;
+				checked_div_err => checked_div, Div;
+				checked_rem_err => checked_rem, Rem;
+			}
+		}
+	)+ };
+}
+
+checked!(
+	u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize,
+);
+
+crate::sealed! {
+	mod seal;
+	u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn success() {
+		assert_eq!(2u8.checked_add_err(3), Ok(5));
+		assert_eq!(2i32.checked_sub_err(3), Ok(-1));
+		assert_eq!(4usize.checked_mul_err(5), Ok(20));
+		assert_eq!(9u64.checked_div_err(2), Ok(4));
+		assert_eq!((-9i16).checked_rem_err(4), Ok(-1));
+	}
+
+	#[test]
+	fn failure() {
+		let err = 200u8.checked_add_err(100).unwrap_err();
+		assert_eq!(err, ArithmeticError {
+			op: Operation::Add,
+			lhs: 200,
+			rhs: 100,
+		});
+		assert!(!err.is_division_by_zero());
+
+		assert!(0u32.checked_sub_err(1).is_err());
+		assert!(i64::MAX.checked_mul_err(2).is_err());
+
+		let err = i8::MIN.checked_div_err(-1).unwrap_err();
+		assert!(!err.is_division_by_zero());
+		let err = 5u8.checked_rem_err(0).unwrap_err();
+		assert!(err.is_division_by_zero());
+		assert_eq!(err.op, Operation::Rem);
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn render() {
+		use alloc::string::ToString;
+
+		assert_eq!(
+			(-128i8).checked_div_err(-1).unwrap_err().to_string(),
+			"arithmetic overflow in `-128 / -1`",
+		);
+		assert_eq!(
+			1u8.checked_rem_err(0).unwrap_err().to_string(),
+			"division by zero in `1 % 0`",
+		);
+	}
+}
@@ -0,0 +1,239 @@
+/*! `Option` and `Result` combinators
+
+These traits supply combinators that the standard library lacks, or has not yet
+stabilized, so that downstream crates do not each need a private extension
+trait for them.
+
+- [`OptionExt`] fills an `Option` with a fallible initializer.
+- [`ResultExt`] inspects values with borrowed context, and combines `Result`s.
+- [`FlattenExt`] collapses an `Option` and a `Result`, nested in either order,
+  into a single `Result`.
+
+[`OptionExt::try_get_or_insert_with`] mirrors the unstable
+`Option::get_or_try_insert_with`, under a name that will not collide with it.
+!*/
+
+/// Extension methods for `Option`.
+pub trait OptionExt<T> {
+	/// Gets the contained value, or inserts the value produced by `func` if
+	/// there is none. If `func` fails, `self` remains `None` and the error is
+	/// returned.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::combinator::OptionExt;
+	///
+	/// let mut slot = None;
+	/// assert!(slot.try_get_or_insert_with(|| "x".parse::<u8>()).is_err());
+	/// assert!(slot.is_none());
+	///
+	/// assert_eq!(slot.try_get_or_insert_with(|| "5".parse::<u8>()), Ok(&mut 5));
+	/// assert_eq!(slot, Some(5));
+	/// ```
+	fn try_get_or_insert_with<E>(
+		&mut self,
+		func: impl FnOnce() -> Result<T, E>,
+	) -> Result<&mut T, E>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+	#[inline]
+	fn try_get_or_insert_with<E>(
+		&mut self,
+		func: impl FnOnce() -> Result<T, E>,
+	) -> Result<&mut T, E> {
+		if self.is_none() {
+			*self = Some(func()?);
+		}
+		Ok(self.as_mut().expect("the option was just filled"))
+	}
+}
+
+/// Extension methods for `Result`.
+pub trait ResultExt<T, E>: Sized {
+	/// Calls `func` with a reference to the success value and to `ctx`, then
+	/// returns `self` unchanged.
+	fn inspect_with<C>(self, ctx: &C, func: impl FnOnce(&T, &C)) -> Self
+	where C: ?Sized;
+
+	/// Calls `func` with a reference to the error value and to `ctx`, then
+	/// returns `self` unchanged.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::combinator::ResultExt;
+	///
+	/// let mut log = Vec::new();
+	/// let path = "config.toml";
+	/// let res = "x"
+	///  .parse::<u8>()
+	///  .inspect_err_with(path, |err, path| log.push(format!("{}: {}", path, err)));
+	/// assert!(res.is_err());
+	/// assert_eq!(log, ["config.toml: invalid digit found in string"]);
+	/// ```
+	fn inspect_err_with<C>(self, ctx: &C, func: impl FnOnce(&E, &C)) -> Self
+	where C: ?Sized;
+
+	/// Combines two successes into a pair, or produces the first error.
+	#[inline]
+	fn zip<U>(self, other: Result<U, E>) -> Result<(T, U), E> {
+		self.zip_with(other, |a, b| (a, b))
+	}
+
+	/// Combines two successes with `func`, or produces the first error.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::combinator::ResultExt;
+	///
+	/// let width = "3".parse::<u32>();
+	/// let height = "4".parse::<u32>();
+	/// assert_eq!(width.zip_with(height, |w, h| w * h), Ok(12));
+	/// ```
+	fn zip_with<U, R>(
+		self,
+		other: Result<U, E>,
+		func: impl FnOnce(T, U) -> R,
+	) -> Result<R, E>;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E> {
+	#[inline]
+	fn inspect_with<C>(self, ctx: &C, func: impl FnOnce(&T, &C)) -> Self
+	where C: ?Sized {
+		if let Ok(val) = &self {
+			func(val, ctx);
+		}
+		self
+	}
+
+	#[inline]
+	fn inspect_err_with<C>(self, ctx: &C, func: impl FnOnce(&E, &C)) -> Self
+	where C: ?Sized {
+		if let Err(err) = &self {
+			func(err, ctx);
+		}
+		self
+	}
+
+	#[inline]
+	fn zip_with<U, R>(
+		self,
+		other: Result<U, E>,
+		func: impl FnOnce(T, U) -> R,
+	) -> Result<R, E> {
+		Ok(func(self?, other?))
+	}
+}
+
+/// Collapses an `Option` and a `Result`, nested in either order, into one
+/// `Result`.
+pub trait FlattenExt<T, E> {
+	/// Produces the innermost value, the inner or outer error, or `err` if
+	/// the `Option` is `None`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::combinator::FlattenExt;
+	///
+	/// let found: Result<Option<u8>, &str> = Ok(None);
+	/// assert_eq!(found.flatten_or("missing"), Err("missing"));
+	///
+	/// let parsed: Option<Result<u8, &str>> = Some(Ok(5));
+	/// assert_eq!(parsed.flatten_or("missing"), Ok(5));
+	/// ```
+	#[inline]
+	fn flatten_or(self, err: E) -> Result<T, E>
+	where Self: Sized {
+		self.flatten_or_else(|| err)
+	}
+
+	/// Produces the innermost value, the inner or outer error, or the result
+	/// of `func` if the `Option` is `None`.
+	fn flatten_or_else(self, func: impl FnOnce() -> E) -> Result<T, E>;
+}
+
+impl<T, E> FlattenExt<T, E> for Result<Option<T>, E> {
+	#[inline]
+	fn flatten_or_else(self, func: impl FnOnce() -> E) -> Result<T, E> {
+		self?.ok_or_else(func)
+	}
+}
+
+impl<T, E> FlattenExt<T, E> for Option<Result<T, E>> {
+	#[inline]
+	fn flatten_or_else(self, func: impl FnOnce() -> E) -> Result<T, E> {
+		self.ok_or_else(func)?
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::cell::Cell;
+
+	use super::*;
+
+	#[test]
+	fn try_insert() {
+		let mut slot = Some(1);
+		let val = slot
+			.try_get_or_insert_with(|| -> Result<_, ()> { unreachable!() })
+			.unwrap();
+		*val += 1;
+		assert_eq!(slot, Some(2));
+
+		let mut slot = None::<i32>;
+		assert_eq!(slot.try_get_or_insert_with(|| Err("no")), Err("no"));
+		assert_eq!(slot.try_get_or_insert_with(|| Ok::<_, ()>(3)), Ok(&mut 3));
+	}
+
+	#[test]
+	fn inspect() {
+		let seen = Cell::new(0);
+		let ok: Result<i32, i32> = Ok(1);
+		let err: Result<i32, i32> = Err(2);
+		let add = |val: &i32, ctx: &i32| seen.set(seen.get() + val * ctx);
+
+		assert_eq!(ok.inspect_with(&10, add).inspect_err_with(&100, add), ok);
+		assert_eq!(err.inspect_with(&10, add).inspect_err_with(&100, add), err);
+		assert_eq!(seen.get(), 210);
+
+		let ok: Result<u8, ()> = Ok(5);
+		ok.inspect_with("str", |_, ctx: &str| assert_eq!(ctx.len(), 3))
+			.unwrap();
+	}
+
+	#[test]
+	fn zip() {
+		let a: Result<i32, &str> = Ok(1);
+		let b: Result<char, &str> = Ok('b');
+		assert_eq!(a.zip(b), Ok((1, 'b')));
+		assert_eq!(a.zip(Err::<(), _>("second")), Err("second"));
+		assert_eq!(
+			Err::<(), _>("first").zip(Err::<(), _>("second")),
+			Err("first")
+		);
+		assert_eq!(a.zip_with(Ok(2), |x, y| x + y), Ok(3));
+	}
+
+	#[test]
+	fn flatten() {
+		type RO = Result<Option<i32>, &'static str>;
+		type OR = Option<Result<i32, &'static str>>;
+
+		assert_eq!(RO::Ok(Some(1)).flatten_or("none"), Ok(1));
+		assert_eq!(RO::Ok(None).flatten_or("none"), Err("none"));
+		assert_eq!(
+			RO::Err("err").flatten_or_else(|| unreachable!()),
+			Err("err")
+		);
+
+		assert_eq!(OR::Some(Ok(1)).flatten_or("none"), Ok(1));
+		assert_eq!(OR::Some(Err("err")).flatten_or("none"), Err("err"));
+		assert_eq!(OR::None.flatten_or("none"), Err("none"));
+	}
+}
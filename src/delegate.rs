@@ -0,0 +1,185 @@
+/*! `delegate!` macro
+
+Newtype wrappers frequently need to re-expose some of the methods of the type
+they wrap. The `delegate!` macro generates those forwarding methods from their
+signatures, so that each forwarded method is written once, as a declaration,
+rather than as a hand-written body that can drift out of sync.
+
+The macro is invoked inside an `impl` block (inherent or trait). It begins with
+the path to the wrapped value, written as `to self.field:`, and then lists the
+methods to forward, each as a signature ending in `;`. Each generated method
+calls the method of the same name on the wrapped value, passing along all of its
+arguments, and is marked `#[inline]`. Attributes, including doc comments, and
+visibility are copied onto the generated method.
+
+Receivers may be `&self`, `&mut self`, or `self`. Generic methods are not
+supported.
+
+# Examples
+
+```rust
+use wyz::delegate;
+
+struct Stack<T>(Vec<T>);
+
+impl<T> Stack<T> {
+ delegate! {
+  to self.0:
+  /// Counts the items on the stack.
+  pub fn len(&self) -> usize;
+  pub fn is_empty(&self) -> bool;
+  pub fn push(&mut self, item: T);
+  pub fn pop(&mut self) -> Option<T>;
+ }
+}
+
+let mut stack = Stack(Vec::new());
+assert!(stack.is_empty());
+stack.push(5);
+stack.push(6);
+assert_eq!(stack.len(), 2);
+assert_eq!(stack.pop(), Some(6));
+```
+!*/
+
+/// `delegate!` macro
+#[macro_export]
+macro_rules! delegate {
+	( to $recv:ident $( . $field:tt )+ : $( $rest:tt )* ) => {
+		$crate::delegate!(@fns [ $recv $( . $field )+ ] $( $rest )*);
+	};
+
+	( @fns [ $( $target:tt )+ ] ) => {};
+
+	(
+		@fns [ $( $target:tt )+ ]
+		$( #[$attr:meta] )*
+		$vis:vis fn $name:ident (
+			&mut $self:ident $( , $arg:ident : $typ:ty )* $(,)?
+		) $( -> $ret:ty )? ;
+		$( $rest:tt )*
+	) => {
+		$( #[$attr] )*
+		#[inline]
+		$vis fn $name(&mut $self $( , $arg : $typ )*) $( -> $ret )? {
+			$( $target )+ . $name($( $arg ),*)
+		}
+
+		$crate::delegate!(@fns [ $( $target )+ ] $( $rest )*);
+	};
+
+	(
+		@fns [ $( $target:tt )+ ]
+		$( #[$attr:meta] )*
+		$vis:vis fn $name:ident (
+			& $self:ident $( , $arg:ident : $typ:ty )* $(,)?
+		) $( -> $ret:ty )? ;
+		$( $rest:tt )*
+	) => {
+		$( #[$attr] )*
+		#[inline]
+		$vis fn $name(&$self $( , $arg : $typ )*) $( -> $ret )? {
+			$( $target )+ . $name($( $arg ),*)
+		}
+
+		$crate::delegate!(@fns [ $( $target )+ ] $( $rest )*);
+	};
+
+	(
+		@fns [ $( $target:tt )+ ]
+		$( #[$attr:meta] )*
+		$vis:vis fn $name:ident (
+			$self:ident $( , $arg:ident : $typ:ty )* $(,)?
+		) $( -> $ret:ty )? ;
+		$( $rest:tt )*
+	) => {
+		$( #[$attr] )*
+		#[inline]
+		$vis fn $name($self $( , $arg : $typ )*) $( -> $ret )? {
+			$( $target )+ . $name($( $arg ),*)
+		}
+
+		$crate::delegate!(@fns [ $( $target )+ ] $( $rest )*);
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use core::fmt::{
+		self,
+		Write,
+	};
+
+	struct Counter {
+		count: usize,
+	}
+
+	impl Counter {
+		fn get(&self) -> usize {
+			self.count
+		}
+
+		fn add(&mut self, a: usize, b: usize) {
+			self.count += a + b;
+		}
+
+		fn into_inner(self) -> usize {
+			self.count
+		}
+	}
+
+	struct Wrapper {
+		inner: Counter,
+	}
+
+	impl Wrapper {
+		delegate! {
+			to self.inner:
+			fn get(&self) -> usize;
+			fn add(&mut self, a: usize, b: usize,);
+			fn into_inner(self) -> usize;
+		}
+	}
+
+	struct Cursor<'a>(&'a mut [u8], usize);
+
+	impl Write for Cursor<'_> {
+		fn write_str(&mut self, text: &str) -> fmt::Result {
+			let end = self.1 + text.len();
+			self.0
+				.get_mut(self.1 .. end)
+				.ok_or(fmt::Error)?
+				.copy_from_slice(text.as_bytes());
+			self.1 = end;
+			Ok(())
+		}
+	}
+
+	struct Writer<'a>(Cursor<'a>);
+
+	impl Write for Writer<'_> {
+		delegate! {
+			to self.0:
+			fn write_str(&mut self, text: &str) -> fmt::Result;
+		}
+	}
+
+	#[test]
+	fn forwarding() {
+		let mut wrapper = Wrapper {
+			inner: Counter { count: 1 },
+		};
+		wrapper.add(2, 3);
+		assert_eq!(wrapper.get(), 6);
+		assert_eq!(wrapper.into_inner(), 6);
+	}
+
+	#[test]
+	fn trait_forwarding() {
+		let mut buf = [0u8; 16];
+		let mut writer = Writer(Cursor(&mut buf, 0));
+		write!(writer, "{} + {}", 2, 3).unwrap();
+		let Writer(Cursor(buf, len)) = writer;
+		assert_eq!(&buf[.. len], b"2 + 3");
+	}
+}
@@ -0,0 +1,250 @@
+/*! Fixed-endianness integers
+
+On-disk and on-wire formats specify the byte order of each integer field, which
+may differ from the byte order of the machine reading them. The [`BigEndian`]
+and [`LittleEndian`] wrappers store an integer in memory in their named byte
+order, and convert it to and from the native order when it is read or written.
+
+Because they have the same layout as the integer they wrap, they implement
+[`BytesOf`], so that a struct or array of them can be viewed as its encoded
+bytes directly.
+
+# Examples
+
+```rust
+use wyz::{bytes::BytesOf, endian::BigEndian};
+
+let mut len = BigEndian::new(0x0102_0304u32);
+assert_eq!(len.as_bytes(), &[1, 2, 3, 4]);
+
+len.set(5);
+assert_eq!(len.get(), 5);
+assert_eq!(len.as_bytes(), &[0, 0, 0, 5]);
+```
+
+[`BytesOf`]: crate::bytes::BytesOf
+!*/
+
+use core::{
+	cmp::Ordering,
+	fmt::{
+		self,
+		Debug,
+		Display,
+		Formatter,
+	},
+};
+
+use crate::bytes::BytesOf;
+
+/// An integer that can be converted between byte orders.
+pub trait Endian: seal::Sealed + BytesOf + Copy + Ord {
+	/// Converts a native-order integer to big-endian order.
+	fn to_be(self) -> Self;
+
+	/// Converts a big-endian integer to native order.
+	fn from_be(raw: Self) -> Self;
+
+	/// Converts a native-order integer to little-endian order.
+	fn to_le(self) -> Self;
+
+	/// Converts a little-endian integer to native order.
+	fn from_le(raw: Self) -> Self;
+}
+
+macro_rules! endian_int {
+	($($t:ident),+ $(,)?) => { $(
+		impl Endian for $t {
+			#[inline(always)]
+			fn to_be(self) -> Self {
+				$t::to_be(self)
+			}
+
+			#[inline(always)]
+			fn from_be(raw: Self) -> Self {
+				$t::from_be(raw)
+			}
+
+			#[inline(always)]
+			fn to_le(self) -> Self {
+				$t::to_le(self)
+			}
+
+			#[inline(always)]
+			fn from_le(raw: Self) -> Self {
+				$t::from_le(raw)
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl From<BigEndian<$t>> for $t {
+			#[inline(always)]
+			fn from(value: BigEndian<$t>) -> Self {
+				value.get()
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl From<LittleEndian<$t>> for $t {
+			#[inline(always)]
+			fn from(value: LittleEndian<$t>) -> Self {
+				value.get()
+			}
+		}
+	)+ };
+}
+
+endian_int!(
+	u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+macro_rules! endian {
+	($($(#[$attr:meta])* $name:ident => $to:ident, $from:ident);+ $(;)?) => { $(
+		$(#[$attr])*
+		#[repr(transparent)]
+		#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+		pub struct $name<T>
+		where T: Endian
+		{
+			/// The integer, in the named byte order.
+			raw: T,
+		}
+
+		impl<T> $name<T>
+		where T: Endian
+		{
+			/// Stores a native-order integer in the named byte order.
+			#[inline(always)]
+			pub fn new(value: T) -> Self {
+				Self { raw: value.$to() }
+			}
+
+			/// Wraps an integer that is already in the named byte order, such
+			/// as one read directly from a buffer.
+			#[inline(always)]
+			pub const fn from_raw(raw: T) -> Self {
+				Self { raw }
+			}
+
+			/// Reads the integer in native order.
+			#[inline(always)]
+			pub fn get(self) -> T {
+				T::$from(self.raw)
+			}
+
+			/// Writes a native-order integer.
+			#[inline(always)]
+			pub fn set(&mut self, value: T) {
+				self.raw = value.$to();
+			}
+
+			/// Produces the integer as it is stored, in the named byte order.
+			#[inline(always)]
+			pub const fn to_raw(self) -> T {
+				self.raw
+			}
+		}
+
+		impl<T> BytesOf for $name<T> where T: Endian
+		{
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl<T> From<T> for $name<T>
+		where T: Endian
+		{
+			#[inline(always)]
+			fn from(value: T) -> Self {
+				Self::new(value)
+			}
+		}
+
+		impl<T> Ord for $name<T>
+		where T: Endian
+		{
+			#[inline]
+			fn cmp(&self, other: &Self) -> Ordering {
+				self.get().cmp(&other.get())
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl<T> PartialOrd for $name<T>
+		where T: Endian
+		{
+			#[inline(always)]
+			fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+				Some(self.cmp(other))
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl<T> Debug for $name<T>
+		where T: Endian + Debug
+		{
+			#[inline]
+			fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+				fmt.debug_tuple(stringify!($name)).field(&self.get()).finish()
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl<T> Display for $name<T>
+		where T: Endian + Display
+		{
+			#[inline(always)]
+			fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+				Display::fmt(&self.get(), fmt)
+			}
+		}
+	)+ };
+}
+
+endian! {
+	/// An integer stored in big-endian (network) byte order.
+	BigEndian => to_be, from_be;
+	/// An integer stored in little-endian byte order.
+	LittleEndian => to_le, from_le;
+}
+
+crate::sealed! {
+	mod seal;
+	u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn byte_order() {
+		let be = BigEndian::new(0x0102u16);
+		let le = LittleEndian::new(0x0102u16);
+		assert_eq!(be.as_bytes(), &[1, 2]);
+		assert_eq!(le.as_bytes(), &[2, 1]);
+		assert_eq!(be.get(), le.get());
+		assert_eq!(be.to_raw(), 0x0102u16.to_be());
+
+		let from_wire = BigEndian::from_raw(u32::from_ne_bytes([0, 0, 1, 0]));
+		assert_eq!(u32::from(from_wire), 256);
+	}
+
+	#[test]
+	fn mutation() {
+		let mut header = [LittleEndian::new(0i16); 2];
+		header[0].set(-2);
+		header[1] = 3.into();
+		assert_eq!(header.as_bytes(), &[0xFE, 0xFF, 3, 0]);
+
+		header.as_bytes_mut()[2] = 4;
+		assert_eq!(header[1].get(), 4);
+	}
+
+	#[test]
+	fn ordering() {
+		let small = BigEndian::new(0x00FFu16);
+		let large = BigEndian::new(0x0100u16);
+		assert!(small < large);
+		assert_eq!(small.max(large), large);
+	}
+}
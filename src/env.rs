@@ -0,0 +1,222 @@
+/*! Environment variable parsing
+
+Programs that read configuration from the environment need to distinguish a
+variable that is absent from one that is present but malformed, and should tell
+the user which variable and value were at fault. The [`parse`] and
+[`parse_or`] functions read and parse a variable in one step, and report
+failures with an [`EnvError`] that carries those details.
+
+# Examples
+
+```rust
+use wyz::env;
+
+std::env::set_var("WYZ_DOC_THREADS", "four");
+let err = env::parse::<usize>("WYZ_DOC_THREADS").unwrap_err();
+assert_eq!(
+ err.to_string(),
+ "environment variable `WYZ_DOC_THREADS` has an invalid value \"four\": invalid digit found in string",
+);
+
+let port: u16 = env::parse_or("WYZ_DOC_PORT", 8080).unwrap();
+assert_eq!(port, 8080);
+```
+!*/
+
+#![cfg(feature = "std")]
+
+use core::{
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
+	str::FromStr,
+};
+use std::{
+	env::{
+		self,
+		VarError,
+	},
+	error::Error,
+	ffi::OsString,
+	string::String,
+};
+
+/// Reads the environment variable `name`, and parses its value as a `T`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use wyz::env::{self, EnvError};
+///
+/// std::env::remove_var("WYZ_DOC_MISSING");
+/// let res = env::parse::<u8>("WYZ_DOC_MISSING");
+/// assert!(matches!(res, Err(EnvError::Missing { .. })));
+/// ```
+pub fn parse<T>(name: &str) -> Result<T, EnvError<T::Err>>
+where T: FromStr {
+	match env::var(name) {
+		Ok(value) => match value.parse() {
+			Ok(val) => Ok(val),
+			Err(error) => Err(EnvError::Invalid {
+				name: name.into(),
+				value,
+				error,
+			}),
+		},
+		Err(VarError::NotPresent) => {
+			Err(EnvError::Missing { name: name.into() })
+		},
+		Err(VarError::NotUnicode(value)) => Err(EnvError::NotUnicode {
+			name: name.into(),
+			value,
+		}),
+	}
+}
+
+/// Reads the environment variable `name` and parses its value as a `T`, or
+/// produces `default` if the variable is not set.
+///
+/// A variable that is set, but cannot be parsed, is still an error.
+pub fn parse_or<T>(name: &str, default: T) -> Result<T, EnvError<T::Err>>
+where T: FromStr {
+	parse_or_else(name, || default)
+}
+
+/// Reads the environment variable `name` and parses its value as a `T`, or
+/// calls `default` if the variable is not set.
+///
+/// A variable that is set, but cannot be parsed, is still an error.
+pub fn parse_or_else<T, F>(name: &str, default: F) -> Result<T, EnvError<T::Err>>
+where
+	T: FromStr,
+	F: FnOnce() -> T,
+{
+	match parse(name) {
+		Err(EnvError::Missing { .. }) => Ok(default()),
+		other => other,
+	}
+}
+
+/// Describes why an environment variable could not be parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EnvError<E> {
+	/// The variable is not set.
+	Missing {
+		/// The name of the variable.
+		name: String,
+	},
+	/// The variable is set, but its value is not valid Unicode.
+	NotUnicode {
+		/// The name of the variable.
+		name: String,
+		/// The value of the variable.
+		value: OsString,
+	},
+	/// The variable is set, but its value could not be parsed.
+	Invalid {
+		/// The name of the variable.
+		name: String,
+		/// The value of the variable.
+		value: String,
+		/// The error produced by the parser.
+		error: E,
+	},
+}
+
+impl<E> EnvError<E> {
+	/// Gets the name of the variable that could not be parsed.
+	#[inline]
+	pub fn name(&self) -> &str {
+		match self {
+			Self::Missing { name }
+			| Self::NotUnicode { name, .. }
+			| Self::Invalid { name, .. } => name,
+		}
+	}
+}
+
+impl<E> Display for EnvError<E>
+where E: Display
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Missing { name } => {
+				write!(fmt, "environment variable `{}` is not set", name)
+			},
+			Self::NotUnicode { name, value } => write!(
+				fmt,
+				"environment variable `{}` is not valid Unicode: {:?}",
+				name, value,
+			),
+			Self::Invalid { name, value, error } => write!(
+				fmt,
+				"environment variable `{}` has an invalid value {:?}: {}",
+				name, value, error,
+			),
+		}
+	}
+}
+
+impl<E> Error for EnvError<E>
+where E: 'static + Error
+{
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Invalid { error, .. } => Some(error),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::string::ToString;
+
+	use super::*;
+
+	#[test]
+	fn parsing() {
+		env::set_var("WYZ_TEST_PARSE_OK", "42");
+		assert_eq!(parse::<u32>("WYZ_TEST_PARSE_OK"), Ok(42));
+		assert_eq!(parse_or("WYZ_TEST_PARSE_OK", 7u32), Ok(42));
+
+		env::remove_var("WYZ_TEST_PARSE_MISSING");
+		let err = parse::<u32>("WYZ_TEST_PARSE_MISSING").unwrap_err();
+		assert_eq!(err.name(), "WYZ_TEST_PARSE_MISSING");
+		assert_eq!(
+			err.to_string(),
+			"environment variable `WYZ_TEST_PARSE_MISSING` is not set",
+		);
+		assert!(err.source().is_none());
+		assert_eq!(parse_or("WYZ_TEST_PARSE_MISSING", 7u32), Ok(7));
+		assert_eq!(parse_or_else("WYZ_TEST_PARSE_MISSING", || 8u32), Ok(8));
+	}
+
+	#[test]
+	fn invalid() {
+		env::set_var("WYZ_TEST_PARSE_BAD", "-1");
+		let err = parse_or("WYZ_TEST_PARSE_BAD", 0u8).unwrap_err();
+		assert!(
+			matches!(&err, EnvError::Invalid { value, .. } if value == "-1")
+		);
+		assert!(err.source().is_some());
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn not_unicode() {
+		use std::os::unix::ffi::OsStringExt;
+
+		let value = OsString::from_vec(std::vec![0xFF]);
+		env::set_var("WYZ_TEST_PARSE_BYTES", &value);
+		assert_eq!(
+			parse::<u8>("WYZ_TEST_PARSE_BYTES"),
+			Err(EnvError::NotUnicode {
+				name: "WYZ_TEST_PARSE_BYTES".into(),
+				value,
+			}),
+		);
+	}
+}
@@ -0,0 +1,362 @@
+/*! Error utilities
+
+Libraries frequently need to report failures that do not merit a dedicated
+error type. The [`MsgError`] type carries only a message, and the `err_msg!`
+macro builds one with `format!` syntax. Neither requires `std`; the message is
+stored without allocating when it is a string literal.
+
+# Examples
+
+```rust
+use wyz::{err_msg, error::MsgError};
+
+fn parse_flag(text: &str) -> Result<bool, MsgError> {
+ match text {
+  "on" => Ok(true),
+  "off" => Ok(false),
+  other => Err(err_msg!("expected `on` or `off`, found `{}`", other)),
+ }
+}
+
+assert_eq!(
+ parse_flag("maybe").unwrap_err().to_string(),
+ "expected `on` or `off`, found `maybe`",
+);
+```
+
+With the `std` feature, the `BoxedError` alias names a type-erased error, and
+the `Context` trait wraps the errors in `Result`s, and the absence of a value
+in `Option`s, with a message describing what was being attempted. The original
+error remains reachable through `source()`, so `ErrorChain` can display the
+whole chain.
+
+```rust
+# #[cfg(feature = "std")] {
+use wyz::error::{BoxedError, Context};
+
+fn read_port(text: &str) -> Result<u16, BoxedError> {
+ text.parse::<u16>().context("while reading the port number")
+}
+
+let err = read_port("http").unwrap_err();
+assert_eq!(err.to_string(), "while reading the port number");
+assert!(err.source().is_some());
+# }
+```
+!*/
+
+#![cfg(feature = "alloc")]
+
+#[cfg(feature = "std")]
+use std::{
+	boxed::Box,
+	error::Error,
+};
+
+use alloc::{
+	borrow::Cow,
+	string::String,
+};
+use core::fmt::{
+	self,
+	Arguments,
+	Debug,
+	Display,
+	Formatter,
+};
+
+#[cfg(feature = "std")]
+use crate::exit::ErrorChain;
+
+/// `err_msg!` macro
+#[macro_export]
+macro_rules! err_msg {
+	( $( $arg:tt )+ ) => {
+		$crate::error::MsgError::from_args(::core::format_args!($( $arg )+))
+	};
+}
+
+/** An error that consists only of a message.
+
+## Examples
+
+```rust
+use wyz::error::MsgError;
+
+let err = MsgError::new("connection closed");
+assert_eq!(err.to_string(), "connection closed");
+```
+**/
+#[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct MsgError {
+	/// The error message.
+	msg: Cow<'static, str>,
+}
+
+impl MsgError {
+	/// Creates an error from a static or owned message.
+	#[inline]
+	pub fn new(msg: impl Into<Cow<'static, str>>) -> Self {
+		Self { msg: msg.into() }
+	}
+
+	/// Creates an error from `format_args!` output.
+	///
+	/// This only allocates if the arguments contain values to format.
+	#[inline]
+	pub fn from_args(args: Arguments) -> Self {
+		match args.as_str() {
+			Some(msg) => Self::new(msg),
+			None => Self::new(alloc::fmt::format(args)),
+		}
+	}
+
+	/// Views the error message.
+	#[inline]
+	pub fn as_str(&self) -> &str {
+		&self.msg
+	}
+
+	/// Unwraps the error message.
+	#[inline]
+	pub fn into_inner(self) -> Cow<'static, str> {
+		self.msg
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl Debug for MsgError {
+	#[inline(always)]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_tuple("MsgError").field(&self.as_str()).finish()
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl Display for MsgError {
+	#[inline(always)]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str(self.as_str())
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MsgError {
+}
+
+#[cfg(not(tarpaulin_include))]
+impl From<&'static str> for MsgError {
+	#[inline(always)]
+	fn from(msg: &'static str) -> Self {
+		Self::new(msg)
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl From<String> for MsgError {
+	#[inline(always)]
+	fn from(msg: String) -> Self {
+		Self::new(msg)
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl From<Cow<'static, str>> for MsgError {
+	#[inline(always)]
+	fn from(msg: Cow<'static, str>) -> Self {
+		Self::new(msg)
+	}
+}
+
+/// A type-erased error that can be sent between threads.
+#[cfg(feature = "std")]
+pub type BoxedError = Box<dyn Error + Send + Sync + 'static>;
+
+/// Attaches a description of the failed operation to an error.
+#[cfg(feature = "std")]
+pub trait Context<T>: Sized {
+	/// Wraps the error, or absent value, in a [`ContextError`] that displays
+	/// `context` and keeps the original error as its `source()`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::error::Context;
+	///
+	/// let port: Option<u16> = None;
+	/// let err = port.context("no port configured").unwrap_err();
+	/// assert_eq!(err.to_string(), "no port configured");
+	/// ```
+	fn context<C>(self, context: C) -> Result<T, BoxedError>
+	where C: 'static + Display + Send + Sync;
+
+	/// Wraps the error, or absent value, in a [`ContextError`] whose context
+	/// is only computed when there is an error.
+	fn with_context<C, F>(self, func: F) -> Result<T, BoxedError>
+	where
+		C: 'static + Display + Send + Sync,
+		F: FnOnce() -> C;
+}
+
+#[cfg(feature = "std")]
+impl<T, E> Context<T> for Result<T, E>
+where E: Into<BoxedError>
+{
+	#[inline]
+	fn context<C>(self, context: C) -> Result<T, BoxedError>
+	where C: 'static + Display + Send + Sync {
+		self.with_context(|| context)
+	}
+
+	#[inline]
+	fn with_context<C, F>(self, func: F) -> Result<T, BoxedError>
+	where
+		C: 'static + Display + Send + Sync,
+		F: FnOnce() -> C,
+	{
+		self.map_err(|err| {
+			Box::new(ContextError {
+				context: func(),
+				source: Some(err.into()),
+			}) as BoxedError
+		})
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T> Context<T> for Option<T> {
+	#[inline]
+	fn context<C>(self, context: C) -> Result<T, BoxedError>
+	where C: 'static + Display + Send + Sync {
+		self.with_context(|| context)
+	}
+
+	#[inline]
+	fn with_context<C, F>(self, func: F) -> Result<T, BoxedError>
+	where
+		C: 'static + Display + Send + Sync,
+		F: FnOnce() -> C,
+	{
+		self.ok_or_else(|| {
+			Box::new(ContextError {
+				context: func(),
+				source: None,
+			}) as BoxedError
+		})
+	}
+}
+
+/** An error produced by [`Context`], which describes a failed operation.
+
+It `Display`s only its context message; its `Debug` implementation displays
+the full `source()` chain, so that returning it from `main` prints every cause.
+**/
+#[cfg(feature = "std")]
+pub struct ContextError<C> {
+	/// The description of the failed operation.
+	context: C,
+	/// The error that caused the failure, if any.
+	source: Option<BoxedError>,
+}
+
+#[cfg(feature = "std")]
+impl<C> ContextError<C> {
+	/// Views the description of the failed operation.
+	#[inline]
+	pub fn context(&self) -> &C {
+		&self.context
+	}
+}
+
+#[cfg(feature = "std")]
+impl<C> Debug for ContextError<C>
+where C: Display
+{
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Display::fmt(&ErrorChain(self), fmt)
+	}
+}
+
+#[cfg(feature = "std")]
+#[cfg(not(tarpaulin_include))]
+impl<C> Display for ContextError<C>
+where C: Display
+{
+	#[inline(always)]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.context, fmt)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<C> Error for ContextError<C>
+where C: Display
+{
+	#[inline]
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		self.source
+			.as_deref()
+			.map(|err| err as &(dyn Error + 'static))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::string::ToString;
+
+	use super::*;
+
+	#[test]
+	fn messages() {
+		let err = err_msg!("static");
+		assert!(matches!(err.clone().into_inner(), Cow::Borrowed("static")));
+		assert_eq!(err, MsgError::from("static"));
+
+		let num = 5;
+		let err = err_msg!("found {} items", num);
+		assert!(matches!(err.as_str(), "found 5 items"));
+		assert!(matches!(err.into_inner(), Cow::Owned(_)));
+	}
+
+	#[test]
+	fn render() {
+		let err = MsgError::new(String::from("failed"));
+		assert_eq!(err.to_string(), "failed");
+		assert_eq!(alloc::format!("{:?}", err), r#"MsgError("failed")"#);
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn context() {
+		use std::format;
+
+		let res: Result<u8, _> = "256".parse::<u8>();
+		let err = res
+			.context("parsing the count")
+			.with_context(|| format!("loading {}", "config.toml"))
+			.unwrap_err();
+		assert_eq!(err.to_string(), "loading config.toml");
+		assert_eq!(
+			format!("{:?}", err),
+			"loading config.toml\n  caused by: parsing the count\n  caused by: \
+			 number too large to fit in target type",
+		);
+
+		let ctx = err.downcast_ref::<ContextError<std::string::String>>();
+		assert_eq!(ctx.unwrap().context(), "loading config.toml");
+
+		let err = None::<u8>.context(5).unwrap_err();
+		assert_eq!(err.to_string(), "5");
+		assert!(err.source().is_none());
+		assert_eq!(Some(1).context("unused").unwrap(), 1);
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn boxed() {
+		let err: std::boxed::Box<dyn std::error::Error> = err_msg!("x").into();
+		assert!(err.source().is_none());
+		assert_eq!(err.to_string(), "x");
+	}
+}
@@ -3,6 +3,13 @@
 The `exit!` macro simplifies exiting with an error code, and optionally printing
 an error message prior to exit.
 
+The status code may be any type that implements [`ExitStatus`]: the plain `i32`
+accepted by `std::process::exit`, a `u8`, or one of the [`codes`]. With the
+`exit_code` feature, which requires Rust 1.83, it may also be a
+`std::process::ExitCode`, and the `exit_with` function accepts anything that
+implements `std::process::Termination`, such as the `Result` returned from a
+fallible `main`.
+
 # Examples
 
 This example exits with status `1`.
@@ -17,16 +24,167 @@ This example exits with status `2`.
 wyz::exit!(2);
 ```
 
+Before exiting, the macro flushes the standard output and error streams.
+`std::process::exit` does not run destructors, so without this, any text still
+buffered in `stdout` would be lost.
+
 This example exits with status `3`, and uses `eprintln!` to print an error
 message before exiting. Note that if `stderr` has been closed, this will crash
-the program with a panic due to `SIGPIPE`, and *not* call `process::exit()`.
+the program with a panic due to `SIGPIPE`, and *not* call `process::exit()`. If
+the `RUST_BACKTRACE` environment variable enables backtraces, the message is
+followed by a backtrace of the `exit!` call.
 
 ```rust,should_panic
 wyz::exit!(3, "Error status: {}", "testing");
 ```
+
+This example exits with an `ExitCode`.
+
+```rust,no_run
+# #[cfg(feature = "exit_code")] {
+use std::process::ExitCode;
+
+wyz::exit!(ExitCode::FAILURE);
+# }
+```
+
+The [`codes`] module provides the conventional status codes from BSD’s
+`sysexits.h`, so that programs can exit with a named status instead of a magic
+number.
+
+```rust,should_panic
+use wyz::exit::codes;
+
+wyz::exit!(codes::USAGE, "usage: {} <input>", "prog");
+```
+
+The [`OrExit`] trait collapses the common match-and-exit boilerplate around
+`Result` and `Option` values into a single method call.
+
+```rust,should_panic
+use wyz::exit::OrExit;
+
+let num: i32 = "seven".parse().or_exit(2);
+# drop(num);
+```
+
+Errors frequently carry the most useful part of their diagnostic in their
+`source()` chain. Wrapping an error in `ErrorChain` prints every cause on its
+own line, and composes with `OrExit`:
+
+```rust,no_run
+# #[cfg(feature = "std")] {
+use std::fs;
+use wyz::exit::{ErrorChain, OrExit};
+
+let text = fs::read_to_string("/nonexistent")
+  .map_err(ErrorChain)
+  .or_exit(74);
+# drop(text);
+# }
+```
+
+The `bail!` and `ensure!` macros mirror their namesakes in `anyhow`, but exit
+the process rather than returning an error. `bail!` is an alias of `exit!` that
+reads better at the end of a failure path, and `ensure!` exits only when its
+condition is false.
+
+```rust,should_panic
+let args = ["prog"];
+wyz::ensure!(args.len() > 1, 64, "usage: {} <input>", args[0]);
+```
+
+Small programs that want `?` in their `main` body, but also want control over
+the exit status and the rendering of the error, can use `try_main!` to generate
+their `main` function. It prints the error and its `source()` chain, then exits
+with the given status code (or `1`).
+
+```rust,no_run
+# #[cfg(feature = "std")] {
+wyz::try_main!(65, {
+  let num: u8 = "256".parse()?;
+  println!("{}", num);
+  Ok(())
+});
+# main();
+# }
+```
+
+The `fatal!` macro is the counterpart for states in which the process cannot
+safely continue shutting down: it prints its message, then calls
+`std::process::abort()` rather than `exit()`, so neither the `on_exit` handlers
+nor any other cleanup routines run. Its message is reported the same way as
+`exit!`’s, and the standard streams are still flushed before the abort.
+
+```rust,no_run
+# #[cfg(feature = "std")] {
+wyz::fatal!("invariant violated: {}", "lock poisoned after fork");
+# }
+```
+
+# Logging
+
+With the `log` feature enabled, every message that `exit!` (and the other
+message-bearing functions in this module) prints is also emitted through
+`log::error!` first, so that the final diagnostic of a failing program reaches
+structured log collectors as well as `stderr`.
+
+# `no_std`
+
+When the `std` feature is disabled, there is no process to exit. `exit!` and
+`OrExit` instead call the hook registered with `set_exit_hook`, passing it the
+status code and the message (if any). This allows embedded targets to route
+program termination to semihosting, a debugger breakpoint, or a reset. If no
+hook has been registered, they `panic!` with the message, or with the status
+code when there is no message. The `std`-only items (`fatal!`, `exit_with`, and
+`ErrorChain`) are not available.
 !*/
 
-#![cfg(feature = "std")]
+use core::fmt::{
+	Arguments,
+	Display,
+};
+#[cfg(not(feature = "std"))]
+use core::{
+	mem,
+	ptr,
+	sync::atomic::{
+		AtomicPtr,
+		Ordering,
+	},
+};
+#[cfg(feature = "exit_code")]
+use std::process::{
+	ExitCode,
+	Termination,
+};
+#[cfg(feature = "std")]
+use std::{
+	backtrace::{
+		Backtrace,
+		BacktraceStatus,
+	},
+	boxed::Box,
+	eprintln,
+	error::Error,
+	fmt::{
+		self,
+		Formatter,
+	},
+	io::{
+		self,
+		Write,
+	},
+	panic,
+	process,
+	string::String,
+	sync::{
+		Mutex,
+		MutexGuard,
+		PoisonError,
+	},
+	vec::Vec,
+};
 
 /// `exit!` macro
 #[macro_export]
@@ -36,11 +194,744 @@ macro_rules! exit {
 	};
 
 	( $num:expr $(,)? ) => {
-		::std::process::exit($num);
+		$crate::exit::exit_process($num);
 	};
 
-	( $num:expr, $fmt:expr $( , $arg:expr )* $(,)? ) => {{
-		eprintln!($fmt $( , $arg )*);
+	( $num:expr, $fmt:expr $( , $arg:expr )* $(,)? ) => {
+		$crate::exit::exit_message(
+			$num,
+			::core::format_args!($fmt $( , $arg )*),
+		);
+	};
+}
+
+/// `bail!` macro
+#[macro_export]
+macro_rules! bail {
+	( $num:expr $(,)? ) => {
 		$crate::exit!($num);
-	}};
+	};
+
+	( $num:expr, $fmt:expr $( , $arg:expr )* $(,)? ) => {
+		$crate::exit!($num, $fmt $( , $arg )*);
+	};
+}
+
+/// `ensure!` macro
+#[macro_export]
+macro_rules! ensure {
+	( $cond:expr, $num:expr $(,)? ) => {
+		if !$cond {
+			$crate::exit!($num);
+		}
+	};
+
+	( $cond:expr, $num:expr, $fmt:expr $( , $arg:expr )* $(,)? ) => {
+		if !$cond {
+			$crate::exit!($num, $fmt $( , $arg )*);
+		}
+	};
+}
+
+/// `try_main!` macro
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! try_main {
+	($body:block) => {
+		$crate::try_main!(1, $body);
+	};
+
+	($num:expr, $body:block) => {
+		fn main() {
+			let body: fn() -> ::std::result::Result<
+				(),
+				::std::boxed::Box<dyn ::std::error::Error>,
+			> = || $body;
+			if let ::std::result::Result::Err(err) = body() {
+				let err = $crate::exit::ErrorChain(&*err);
+				$crate::exit!($num, "error: {}", err);
+			}
+		}
+	};
+}
+
+/// `fatal!` macro
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! fatal {
+	() => {
+		$crate::exit::abort_process();
+	};
+
+	( $fmt:expr $( , $arg:expr )* $(,)? ) => {
+		$crate::exit::abort_message(::core::format_args!($fmt $( , $arg )*));
+	};
+}
+
+/// Conventional exit status codes, as defined in BSD’s `sysexits.h`.
+///
+/// These are [`SysExit`] values, which can be passed directly to `exit!`, and
+/// converted into an `ExitCode`, `u8`, or `i32`.
+///
+/// [`SysExit`]: codes::SysExit
+pub mod codes {
+	#[cfg(feature = "std")]
+	use std::process::ExitCode;
+
+	use super::ExitStatus;
+
+	/// A status code from `sysexits.h`.
+	#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+	#[repr(transparent)]
+	pub struct SysExit(u8);
+
+	impl SysExit {
+		/// Gets the numeric value of the status code.
+		#[inline(always)]
+		pub const fn value(self) -> u8 {
+			self.0
+		}
+	}
+
+	impl ExitStatus for SysExit {
+		#[inline(always)]
+		fn code(self) -> i32 {
+			self.0 as i32
+		}
+	}
+
+	#[cfg(not(tarpaulin_include))]
+	impl From<SysExit> for u8 {
+		#[inline(always)]
+		fn from(code: SysExit) -> Self {
+			code.0
+		}
+	}
+
+	#[cfg(not(tarpaulin_include))]
+	impl From<SysExit> for i32 {
+		#[inline(always)]
+		fn from(code: SysExit) -> Self {
+			code.0 as i32
+		}
+	}
+
+	#[cfg(feature = "std")]
+	#[cfg(not(tarpaulin_include))]
+	impl From<SysExit> for ExitCode {
+		#[inline(always)]
+		fn from(code: SysExit) -> Self {
+			Self::from(code.0)
+		}
+	}
+
+	/// Successful termination.
+	pub const OK: SysExit = SysExit(0);
+
+	/// The command was used incorrectly, such as with the wrong number of
+	/// arguments, a bad flag, or bad syntax in a parameter.
+	pub const USAGE: SysExit = SysExit(64);
+
+	/// The input data was incorrect in some way.
+	pub const DATAERR: SysExit = SysExit(65);
+
+	/// An input file did not exist or was not readable.
+	pub const NOINPUT: SysExit = SysExit(66);
+
+	/// The specified user did not exist.
+	pub const NOUSER: SysExit = SysExit(67);
+
+	/// The specified host did not exist.
+	pub const NOHOST: SysExit = SysExit(68);
+
+	/// A service is unavailable, or a support program or file does not exist.
+	pub const UNAVAILABLE: SysExit = SysExit(69);
+
+	/// An internal software error has been detected.
+	pub const SOFTWARE: SysExit = SysExit(70);
+
+	/// An operating system error has been detected, such as being unable to
+	/// fork or create a pipe.
+	pub const OSERR: SysExit = SysExit(71);
+
+	/// A system file does not exist, cannot be opened, or has a syntax error.
+	pub const OSFILE: SysExit = SysExit(72);
+
+	/// A user-specified output file cannot be created.
+	pub const CANTCREAT: SysExit = SysExit(73);
+
+	/// An error occurred while doing I/O on some file.
+	pub const IOERR: SysExit = SysExit(74);
+
+	/// A temporary failure, indicating something that is not really an error.
+	/// The user is invited to retry later.
+	pub const TEMPFAIL: SysExit = SysExit(75);
+
+	/// The remote system returned something that was impossible during a
+	/// protocol exchange.
+	pub const PROTOCOL: SysExit = SysExit(76);
+
+	/// The user did not have sufficient permission to perform the operation.
+	pub const NOPERM: SysExit = SysExit(77);
+
+	/// Something was found in an unconfigured or misconfigured state.
+	pub const CONFIG: SysExit = SysExit(78);
+}
+
+/// Values which can be used as the status code of `exit!`.
+pub trait ExitStatus {
+	/// Converts the value into the integer handed to `std::process::exit`.
+	fn code(self) -> i32;
+}
+
+impl ExitStatus for i32 {
+	#[inline(always)]
+	fn code(self) -> i32 {
+		self
+	}
+}
+
+impl ExitStatus for u8 {
+	#[inline(always)]
+	fn code(self) -> i32 {
+		self as i32
+	}
+}
+
+/// This conversion is lossy.
+///
+/// `ExitCode` is opaque, so its value is recovered by comparison against every
+/// code that can be constructed from a `u8`. This covers `SUCCESS`, `FAILURE`,
+/// and every `ExitCode::from(u8)`, but codes built through platform-specific
+/// extensions, which may lie outside the `u8` range, are reported as `1`.
+///
+/// `ExitCode` cannot be compared before Rust 1.83, so this requires the
+/// `exit_code` feature.
+#[cfg(feature = "exit_code")]
+impl ExitStatus for ExitCode {
+	fn code(self) -> i32 {
+		(0 ..= u8::MAX)
+			.find(|&n| ExitCode::from(n) == self)
+			.map(i32::from)
+			.unwrap_or(1)
+	}
+}
+
+/// Runs the registered exit handlers and flushes the standard streams, then
+/// exits the process with a status code.
+///
+/// This is the function that `exit!` calls. Errors encountered while flushing
+/// are ignored, as the process is exiting regardless.
+#[cfg(feature = "std")]
+pub fn exit_process<S>(status: S) -> !
+where S: ExitStatus {
+	let code = status.code();
+	run_exit_handlers();
+	flush_std();
+	process::exit(code)
+}
+
+/// Handlers registered by `on_exit`, in registration order.
+#[cfg(feature = "std")]
+static EXIT_HANDLERS: Mutex<Vec<Box<dyn FnOnce() + Send>>> =
+	Mutex::new(Vec::new());
+
+/// Registers a function to run when the process exits through `exit!`.
+///
+/// `std::process::exit` does not run destructors, so this is the place to
+/// flush logs, remove temporary files, or release other external resources.
+/// Handlers run in the reverse order of their registration, before the
+/// standard streams are flushed. A handler may register further handlers,
+/// which run next.
+///
+/// Handlers do not run when the process exits by returning from `main`, or
+/// when it aborts through `fatal!`.
+///
+/// ## Examples
+///
+/// ```rust,should_panic
+/// use wyz::exit::on_exit;
+///
+/// on_exit(|| println!("second"));
+/// on_exit(|| println!("first"));
+/// wyz::exit!(2);
+/// ```
+#[cfg(feature = "std")]
+pub fn on_exit<F>(handler: F)
+where F: 'static + FnOnce() + Send {
+	lock_handlers().push(Box::new(handler));
+}
+
+/// Runs, and unregisters, every handler registered with `on_exit`.
+#[cfg(feature = "std")]
+fn run_exit_handlers() {
+	//  The lock is released before each handler runs, so that handlers can
+	//  register more handlers without deadlocking.
+	loop {
+		let handler = lock_handlers().pop();
+		match handler {
+			Some(handler) => handler(),
+			None => break,
+		}
+	}
+}
+
+/// Locks the exit-handler list, ignoring poison.
+#[cfg(feature = "std")]
+fn lock_handlers() -> MutexGuard<'static, Vec<Box<dyn FnOnce() + Send>>> {
+	EXIT_HANDLERS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Calls the registered exit hook with a status code, or panics if there is
+/// none.
+///
+/// This is the function that `exit!` calls.
+#[cfg(not(feature = "std"))]
+pub fn exit_process<S>(status: S) -> !
+where S: ExitStatus {
+	let code = status.code();
+	match exit_hook() {
+		Some(hook) => hook(code, None),
+		None => panic!("exited with status {}", code),
+	}
+}
+
+/// Prints a message to `stderr`, then exits the process with a status code.
+///
+/// This is the function that `exit!` calls when it is given a message. When
+/// backtraces are enabled by the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`
+/// environment variables, a backtrace of the call site is printed after the
+/// message.
+#[cfg(feature = "std")]
+pub fn exit_message<S>(status: S, msg: Arguments) -> !
+where S: ExitStatus {
+	report(msg);
+	exit_process(status)
+}
+
+/// Flushes the standard streams, then aborts the process.
+///
+/// This is the function that `fatal!` calls. The `on_exit` handlers do not
+/// run.
+#[cfg(feature = "std")]
+pub fn abort_process() -> ! {
+	flush_std();
+	process::abort()
+}
+
+/// Prints a message to `stderr`, then aborts the process.
+///
+/// This is the function that `fatal!` calls when it is given a message. The
+/// message is reported exactly as `exit_message` reports it.
+#[cfg(feature = "std")]
+pub fn abort_message(msg: Arguments) -> ! {
+	report(msg);
+	abort_process()
+}
+
+/// Emits a message through `log` (when enabled) and `stderr`, followed by a
+/// backtrace when backtraces are enabled.
+#[cfg(feature = "std")]
+fn report(msg: Arguments) {
+	#[cfg(feature = "log")]
+	log::error!("{}", msg);
+	eprintln!("{}", msg);
+	let trace = Backtrace::capture();
+	if let BacktraceStatus::Captured = trace.status() {
+		eprintln!("stack backtrace:\n{}", trace);
+	}
+}
+
+/// Calls the registered exit hook with a status code and message, or panics
+/// with the message if there is no hook.
+///
+/// This is the function that `exit!` calls when it is given a message.
+#[cfg(not(feature = "std"))]
+pub fn exit_message<S>(status: S, msg: Arguments) -> !
+where S: ExitStatus {
+	#[cfg(feature = "log")]
+	log::error!("{}", msg);
+	let code = status.code();
+	match exit_hook() {
+		Some(hook) => hook(code, Some(msg)),
+		None => panic!("{}", msg),
+	}
+}
+
+/// A function that terminates the program in `no_std` environments.
+///
+/// It receives the status code, and the message given to `exit!`, if any.
+#[cfg(not(feature = "std"))]
+pub type ExitHook = fn(i32, Option<Arguments>) -> !;
+
+/// The registered `ExitHook`, stored as a type-erased pointer.
+#[cfg(not(feature = "std"))]
+static EXIT_HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Registers the function that `exit!` calls in `no_std` environments.
+///
+/// Later registrations replace earlier ones.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use core::fmt::Arguments;
+///
+/// fn semihost_exit(code: i32, msg: Option<Arguments>) -> ! {
+///   // report `msg` to the host, then request termination with `code`.
+///   loop {}
+/// }
+///
+/// wyz::exit::set_exit_hook(semihost_exit);
+/// ```
+#[cfg(not(feature = "std"))]
+pub fn set_exit_hook(hook: ExitHook) {
+	EXIT_HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Loads the registered exit hook, if any.
+#[cfg(not(feature = "std"))]
+fn exit_hook() -> Option<ExitHook> {
+	let hook = EXIT_HOOK.load(Ordering::Acquire);
+	if hook.is_null() {
+		return None;
+	}
+	//  SAFETY: the only non-null values ever stored are `ExitHook` pointers.
+	Some(unsafe { mem::transmute::<*mut (), ExitHook>(hook) })
+}
+
+/// Exits the process with the status reported by a `Termination` value.
+///
+/// This runs `status.report()`, so any printing that the `Termination`
+/// implementation performs (such as the `Error: …` line emitted for an `Err`
+/// result) occurs before the process exits.
+///
+/// The reported `ExitCode` is converted through its lossy `ExitStatus`
+/// implementation, so a code outside the `u8` range exits with status `1`.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "exit_code")] {
+/// let res: Result<(), &str> = Err("could not open the file");
+/// wyz::exit::exit_with(res);
+/// # }
+/// ```
+#[cfg(feature = "exit_code")]
+pub fn exit_with<T>(status: T) -> !
+where T: Termination {
+	exit_process(status.report())
+}
+
+/** Displays an error followed by each error in its `source()` chain.
+
+Each cause is printed on its own indented line, beneath the error that it
+caused.
+
+## Examples
+
+```rust
+# #[cfg(feature = "std")] {
+use wyz::exit::ErrorChain;
+
+let err = "12a".parse::<u8>().unwrap_err();
+assert_eq!(
+  ErrorChain(&err).to_string(),
+  "invalid digit found in string",
+);
+# }
+```
+**/
+#[cfg(feature = "std")]
+#[repr(transparent)]
+pub struct ErrorChain<E>(pub E)
+where E: Error;
+
+#[cfg(feature = "std")]
+impl<E> Display for ErrorChain<E>
+where E: Error
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.0, fmt)?;
+		let mut source = self.0.source();
+		while let Some(err) = source {
+			write!(fmt, "\n  caused by: {}", err)?;
+			source = err.source();
+		}
+		Ok(())
+	}
+}
+
+/// Unwraps a value, or exits the process.
+pub trait OrExit: Sized {
+	/// The type produced when `self` holds a value.
+	type Output;
+
+	/// Produces the contained value, or exits with `status`.
+	///
+	/// An `Err` has its error `Display`ed to `stderr` before exiting; a `None`
+	/// exits without printing anything.
+	///
+	/// ## Examples
+	///
+	/// ```rust,should_panic
+	/// use wyz::exit::OrExit;
+	///
+	/// let first = std::env::args().nth(100).or_exit(64);
+	/// # drop(first);
+	/// ```
+	fn or_exit<S>(self, status: S) -> Self::Output
+	where S: ExitStatus;
+
+	/// Produces the contained value, or prints `msg` and exits with `status`.
+	///
+	/// An `Err` has its error `Display`ed after the message, separated by a
+	/// colon.
+	///
+	/// ## Examples
+	///
+	/// ```rust,should_panic
+	/// use wyz::exit::OrExit;
+	///
+	/// let path = "/nonexistent/config.toml";
+	/// let text = std::fs::read_to_string(path)
+	///   .or_exit_with(66, format_args!("could not read {}", path));
+	/// # drop(text);
+	/// ```
+	fn or_exit_with<S, M>(self, status: S, msg: M) -> Self::Output
+	where
+		S: ExitStatus,
+		M: Display;
+}
+
+impl<T, E> OrExit for Result<T, E>
+where E: Display
+{
+	type Output = T;
+
+	fn or_exit<S>(self, status: S) -> T
+	where S: ExitStatus {
+		match self {
+			Ok(val) => val,
+			Err(err) => exit_message(status, format_args!("{}", err)),
+		}
+	}
+
+	fn or_exit_with<S, M>(self, status: S, msg: M) -> T
+	where
+		S: ExitStatus,
+		M: Display,
+	{
+		match self {
+			Ok(val) => val,
+			Err(err) => exit_message(status, format_args!("{}: {}", msg, err)),
+		}
+	}
+}
+
+impl<T> OrExit for Option<T> {
+	type Output = T;
+
+	fn or_exit<S>(self, status: S) -> T
+	where S: ExitStatus {
+		match self {
+			Some(val) => val,
+			None => exit_process(status),
+		}
+	}
+
+	fn or_exit_with<S, M>(self, status: S, msg: M) -> T
+	where
+		S: ExitStatus,
+		M: Display,
+	{
+		match self {
+			Some(val) => val,
+			None => exit_message(status, format_args!("{}", msg)),
+		}
+	}
+}
+
+/// Installs a panic hook that exits the process with `status`.
+///
+/// By default, a panic in the main thread terminates the process with status
+/// `101`. This replaces the panic hook with one that prints the panic message
+/// (when the payload is a string) and its location, then exits through
+/// `exit!` with the chosen status, so that the `on_exit` handlers still run.
+///
+/// Because the hook exits the process, a panic on *any* thread terminates the
+/// whole program, and panics are no longer catchable with `catch_unwind`.
+///
+/// ## Examples
+///
+/// ```rust,should_panic
+/// wyz::exit::install_panic_handler(wyz::exit::codes::SOFTWARE);
+/// panic!("unexpected state: {}", 5);
+/// ```
+#[cfg(feature = "std")]
+pub fn install_panic_handler<S>(status: S)
+where S: ExitStatus {
+	let code = status.code();
+	panic::set_hook(Box::new(move |info| {
+		let payload = info.payload();
+		let msg = payload
+			.downcast_ref::<&str>()
+			.copied()
+			.or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+			.unwrap_or("Box<dyn Any>");
+		match info.location() {
+			Some(loc) => {
+				exit_message(code, format_args!("panicked at {}:\n{}", loc, msg))
+			},
+			None => exit_message(code, format_args!("panicked:\n{}", msg)),
+		}
+	}));
+}
+
+/// Runs a fallible `main` body, exiting with `status` if it fails.
+///
+/// The error may be any `Display` type, and is printed to `stderr` through
+/// that implementation. To print an `Error`’s `source()` chain as well, as
+/// `try_main!` does, wrap it in an `ErrorChain`.
+///
+/// ## Examples
+///
+/// ```rust,should_panic
+/// use wyz::exit::ErrorChain;
+///
+/// wyz::exit::run(2, || {
+///   std::fs::read("/nonexistent").map_err(ErrorChain)?;
+///   Ok::<(), ErrorChain<std::io::Error>>(())
+/// });
+/// ```
+#[cfg(feature = "std")]
+pub fn run<S, F, E>(status: S, main: F)
+where
+	S: ExitStatus,
+	F: FnOnce() -> Result<(), E>,
+	E: Display,
+{
+	if let Err(err) = main() {
+		exit_message(status, format_args!("error: {}", err))
+	}
+}
+
+/// Flushes `stdout` and `stderr`, ignoring any errors.
+#[cfg(feature = "std")]
+fn flush_std() {
+	let _ = io::stdout().lock().flush();
+	let _ = io::stderr().lock().flush();
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+	use std::string::ToString;
+
+	use super::*;
+
+	#[test]
+	fn status_codes() {
+		assert_eq!(3i32.code(), 3);
+		assert_eq!(200u8.code(), 200);
+		assert_eq!(codes::USAGE.code(), 64);
+		assert_eq!(u8::from(codes::CONFIG), 78);
+	}
+
+	#[test]
+	#[cfg(feature = "exit_code")]
+	fn exit_codes() {
+		assert_eq!(ExitCode::SUCCESS.code(), 0);
+		assert_eq!(ExitCode::FAILURE.code(), 1);
+		assert_eq!(ExitCode::from(64).code(), 64);
+		assert_eq!(Ok::<(), ()>(()).report().code(), 0);
+		assert_eq!(ExitCode::from(codes::SOFTWARE).code(), 70);
+	}
+
+	#[test]
+	fn error_chain() {
+		#[derive(Debug)]
+		struct Outer(Inner);
+		#[derive(Debug)]
+		struct Inner;
+
+		impl Display for Outer {
+			fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+				fmt.write_str("could not load config")
+			}
+		}
+
+		impl Display for Inner {
+			fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+				fmt.write_str("permission denied")
+			}
+		}
+
+		impl Error for Outer {
+			fn source(&self) -> Option<&(dyn Error + 'static)> {
+				Some(&self.0)
+			}
+		}
+
+		impl Error for Inner {
+		}
+
+		assert_eq!(
+			ErrorChain(Outer(Inner)).to_string(),
+			"could not load config\n  caused by: permission denied",
+		);
+		assert_eq!(ErrorChain(Inner).to_string(), "permission denied");
+	}
+
+	#[test]
+	fn ensure_passes() {
+		crate::ensure!(true, 1);
+		crate::ensure!(1 + 1 == 2, 1, "arithmetic is broken");
+	}
+
+	#[test]
+	fn exit_handlers() {
+		use std::sync::Arc;
+
+		let log = Arc::new(Mutex::new(Vec::new()));
+		for n in 0 .. 3 {
+			let log = log.clone();
+			on_exit(move || log.lock().unwrap().push(n));
+		}
+		let inner = log.clone();
+		on_exit(move || {
+			let log = inner.clone();
+			on_exit(move || log.lock().unwrap().push(10));
+			inner.lock().unwrap().push(3);
+		});
+
+		run_exit_handlers();
+		assert_eq!(*log.lock().unwrap(), [3, 10, 2, 1, 0]);
+	}
+
+	#[test]
+	fn run_passes() {
+		run(1, || Ok::<(), &str>(()));
+	}
+
+	#[test]
+	fn or_exit_passes_values() {
+		assert_eq!(Ok::<_, &str>(5).or_exit(1), 5);
+		assert_eq!(Some(6).or_exit_with(1, "unreachable"), 6);
+	}
+}
+
+#[cfg(all(test, not(feature = "std")))]
+mod tests {
+	use super::*;
+
+	#[test]
+	#[should_panic(expected = "hooked 4: message")]
+	fn exit_hook() {
+		fn hook(code: i32, msg: Option<Arguments>) -> ! {
+			panic!("hooked {}: {}", code, msg.unwrap())
+		}
+
+		set_exit_hook(hook);
+		crate::exit!(4, "message");
+	}
 }
@@ -11,9 +11,18 @@ Any modifiers in the format template string or struct modifier are passed
 through to the desired trait implementation unchanged. The only effect of the
 forwarding types in this module is to change the `?` template character to one
 of the other trait signifiers.
+
+The `PrettyDebug` wrapper performs the inverse: it forwards `Display` to the
+original `Debug` implementation, so that types which only derive `Debug` can be
+sent to sinks that require `Display`.
+
+The `FmtCStr` and `FmtOsStr` wrappers display C and OS strings without first
+converting them to `String`s. Bytes that are not valid UTF-8 are written as
+`\xNN` escapes, and the strings can optionally be quoted.
 !*/
 
 use core::{
+	ffi::CStr,
 	fmt::{
 		self,
 		Binary,
@@ -26,12 +35,16 @@ use core::{
 		Pointer,
 		UpperExp,
 		UpperHex,
+		Write,
 	},
 	ops::{
 		Deref,
 		DerefMut,
 	},
+	str,
 };
+#[cfg(feature = "std")]
+use std::ffi::OsStr;
 
 /// Wraps any value with a format-forward to `Debug`.
 #[cfg(not(tarpaulin_include))]
@@ -279,6 +292,178 @@ pub struct FmtUpperExp<T: UpperExp>(pub T);
 #[repr(transparent)]
 pub struct FmtUpperHex<T: UpperHex>(pub T);
 
+/** Forwards a type’s `Debug` formatting implementation to `Display`.
+
+This is the inverse of the `Fmt*` wrappers: it allows a type which only
+implements `Debug` to be used where `Display` is required. The alternate flag
+(`{:#}`) is passed through, and selects the pretty-printed `Debug` rendering.
+
+## Examples
+
+```rust
+# #[cfg(feature = "std")] {
+use wyz::fmt::PrettyDebug;
+
+#[derive(Debug)]
+struct Point {
+  x: i32,
+  y: i32,
+}
+
+let pt = PrettyDebug(Point { x: 1, y: 2 });
+assert_eq!(format!("{}", pt), "Point { x: 1, y: 2 }");
+assert_eq!(format!("{:#}", pt), "Point {\n    x: 1,\n    y: 2,\n}");
+# }
+```
+**/
+#[repr(transparent)]
+pub struct PrettyDebug<T: Debug>(pub T);
+
+/** Displays a C string, escaping any bytes that are not valid UTF-8.
+
+Valid UTF-8 text is written unchanged, and each invalid byte is written as a
+`\xNN` escape. This does not allocate, unlike `CStr::to_string_lossy`, and does
+not lose the values of the invalid bytes.
+
+## Examples
+
+```rust
+# #[cfg(feature = "std")] {
+use std::ffi::CStr;
+use wyz::fmt::FmtCStr;
+
+let name = CStr::from_bytes_with_nul(b"caf\xE9.txt\0").unwrap();
+assert_eq!(FmtCStr::new(name).to_string(), r"caf\xE9.txt");
+assert_eq!(FmtCStr::new(name).quoted().to_string(), r#""caf\xE9.txt""#);
+# }
+```
+**/
+#[derive(Clone, Copy)]
+pub struct FmtCStr<'a> {
+	/// The string to display.
+	inner: &'a CStr,
+	/// Whether to surround the string with quotes and escape its contents.
+	quoted: bool,
+}
+
+impl<'a> FmtCStr<'a> {
+	/// Wraps a C string for display.
+	#[inline]
+	pub fn new(inner: &'a CStr) -> Self {
+		Self {
+			inner,
+			quoted: false,
+		}
+	}
+
+	/// Surrounds the displayed string with double quotes, and escapes its
+	/// contents as `str`’s `Debug` implementation does.
+	#[inline]
+	pub fn quoted(self) -> Self {
+		Self {
+			quoted: true,
+			..self
+		}
+	}
+}
+
+/** Displays an OS string, escaping any contents that are not valid UTF-8.
+
+On Unix, each byte that is not part of valid UTF-8 is written as a `\xNN`
+escape. On other platforms, invalid contents are written as the replacement
+character, `U+FFFD`. Valid strings are written without allocating.
+
+## Examples
+
+```rust
+use std::ffi::OsStr;
+use wyz::fmt::FmtOsStr;
+
+let path = OsStr::new("dir/file name");
+assert_eq!(FmtOsStr::new(path).to_string(), "dir/file name");
+assert_eq!(FmtOsStr::new(path).quoted().to_string(), r#""dir/file name""#);
+```
+**/
+#[cfg(feature = "std")]
+#[derive(Clone, Copy)]
+pub struct FmtOsStr<'a> {
+	/// The string to display.
+	inner: &'a OsStr,
+	/// Whether to surround the string with quotes and escape its contents.
+	quoted: bool,
+}
+
+#[cfg(feature = "std")]
+impl<'a> FmtOsStr<'a> {
+	/// Wraps an OS string for display.
+	#[inline]
+	pub fn new(inner: &'a OsStr) -> Self {
+		Self {
+			inner,
+			quoted: false,
+		}
+	}
+
+	/// Surrounds the displayed string with double quotes, and escapes its
+	/// contents as `str`’s `Debug` implementation does.
+	#[inline]
+	pub fn quoted(self) -> Self {
+		Self {
+			quoted: true,
+			..self
+		}
+	}
+}
+
+/// Writes text, escaping it if it is being quoted.
+fn write_text(text: &str, quoted: bool, fmt: &mut Formatter) -> fmt::Result {
+	if quoted {
+		text.chars().try_for_each(|ch| {
+			ch.escape_debug().try_for_each(|esc| fmt.write_char(esc))
+		})
+	}
+	else {
+		fmt.write_str(text)
+	}
+}
+
+/// Writes bytes as UTF-8 text, escaping each byte that is not valid UTF-8.
+fn write_lossy(
+	mut bytes: &[u8],
+	quoted: bool,
+	fmt: &mut Formatter,
+) -> fmt::Result {
+	if quoted {
+		fmt.write_char('"')?;
+	}
+	loop {
+		match str::from_utf8(bytes) {
+			Ok(text) => {
+				write_text(text, quoted, fmt)?;
+				break;
+			},
+			Err(err) => {
+				let (valid, rest) = bytes.split_at(err.valid_up_to());
+				//  SAFETY: `valid_up_to` marks the end of valid UTF-8.
+				write_text(
+					unsafe { str::from_utf8_unchecked(valid) },
+					quoted,
+					fmt,
+				)?;
+				let invalid = err.error_len().unwrap_or(rest.len());
+				for byte in &rest[.. invalid] {
+					write!(fmt, "\\x{:02X}", byte)?;
+				}
+				bytes = &rest[invalid ..];
+			},
+		}
+	}
+	if quoted {
+		fmt.write_char('"')?;
+	}
+	Ok(())
+}
+
 macro_rules! fmt {
 	($($w:ty => $t:ident),* $(,)?) => { $(
 		#[cfg(not(tarpaulin_include))]
@@ -417,7 +602,7 @@ where
 	for<'a> <&'a T as IntoIterator>::Item: Debug,
 {
 	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
-		fmt.debug_list().entries((&self.0).into_iter()).finish()
+		fmt.debug_list().entries(&self.0).finish()
 	}
 }
 
@@ -535,6 +720,100 @@ where for<'a> &'a T: IntoIterator
 	}
 }
 
+impl<T: Debug> Debug for PrettyDebug<T> {
+	#[inline(always)]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Debug::fmt(&self.0, fmt)
+	}
+}
+
+impl<T: Debug> Display for PrettyDebug<T> {
+	#[inline(always)]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Debug::fmt(&self.0, fmt)
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T: Debug> Deref for PrettyDebug<T> {
+	type Target = T;
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T: Debug> DerefMut for PrettyDebug<T> {
+	#[inline(always)]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T: Debug> AsRef<T> for PrettyDebug<T> {
+	#[inline(always)]
+	fn as_ref(&self) -> &T {
+		&self.0
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T: Debug> AsMut<T> for PrettyDebug<T> {
+	#[inline(always)]
+	fn as_mut(&mut self) -> &mut T {
+		&mut self.0
+	}
+}
+
+impl Debug for FmtCStr<'_> {
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		write_lossy(self.inner.to_bytes(), true, fmt)
+	}
+}
+
+impl Display for FmtCStr<'_> {
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		write_lossy(self.inner.to_bytes(), self.quoted, fmt)
+	}
+}
+
+#[cfg(feature = "std")]
+impl Debug for FmtOsStr<'_> {
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.quoted(), fmt)
+	}
+}
+
+#[cfg(feature = "std")]
+impl Display for FmtOsStr<'_> {
+	#[cfg(unix)]
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		use std::os::unix::ffi::OsStrExt;
+
+		write_lossy(self.inner.as_bytes(), self.quoted, fmt)
+	}
+
+	#[cfg(not(unix))]
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		if self.quoted {
+			fmt.write_char('"')?;
+		}
+		write_text(&self.inner.to_string_lossy(), self.quoted, fmt)?;
+		if self.quoted {
+			fmt.write_char('"')?;
+		}
+		Ok(())
+	}
+}
+
 #[cfg(all(test, feature = "alloc"))]
 mod tests {
 	#[cfg(not(feature = "std"))]
@@ -587,4 +866,35 @@ mod tests {
 			"[00, 0a, 14, 1e]"
 		);
 	}
+
+	#[test]
+	fn render_debug() {
+		let text = "hello\nworld";
+		assert_eq!(format!("{}", PrettyDebug(text)), r#""hello\nworld""#);
+
+		let pair = (1, 2);
+		assert_eq!(format!("{}", PrettyDebug(pair)), "(1, 2)");
+		assert_eq!(format!("{:#}", PrettyDebug(pair)), "(\n    1,\n    2,\n)");
+	}
+
+	#[test]
+	fn render_cstr() {
+		let text =
+			CStr::from_bytes_with_nul(b"a\"\xFF\xC3\n\xC3\xA9\0").unwrap();
+		assert_eq!(format!("{}", FmtCStr::new(text)), "a\"\\xFF\\xC3\né");
+		assert_eq!(format!("{:?}", FmtCStr::new(text)), r#""a\"\xFF\xC3\né""#,);
+
+		let text = CStr::from_bytes_with_nul(b"\xE2\x82\0").unwrap();
+		assert_eq!(format!("{}", FmtCStr::new(text)), r"\xE2\x82");
+	}
+
+	#[test]
+	#[cfg(all(feature = "std", unix))]
+	fn render_os_str() {
+		use std::os::unix::ffi::OsStrExt;
+
+		let text = OsStr::from_bytes(b"x\x80y");
+		assert_eq!(format!("{}", FmtOsStr::new(text)), r"x\x80y");
+		assert_eq!(format!("{:?}", FmtOsStr::new(text)), r#""x\x80y""#);
+	}
 }
@@ -0,0 +1,136 @@
+/*! Function combinators
+
+These functions build new functions out of existing ones. They complement the
+[`Pipe`] trait, which threads a value through functions, by assembling the
+functions themselves, such as when passing a pipeline to an iterator adapter or
+storing it as a callback.
+
+Each combinator returns a closure, so there is no boxing or dynamic dispatch.
+The closures are `FnMut`, so that they accept stateful functions and can be
+passed to any iterator adapter.
+
+# Examples
+
+```rust
+use wyz::func::{compose, pipe_fn};
+
+let words = ["a", "bb", "ccc"];
+let doubled_lens: Vec<usize> = words
+ .iter()
+ .copied()
+ .map(pipe_fn(str::len, |n| n * 2))
+ .collect();
+assert_eq!(doubled_lens, [2, 4, 6]);
+
+let mut negate_abs = compose(|x: i32| -x, i32::abs);
+assert_eq!(negate_abs(-5), -5);
+```
+
+[`Pipe`]: crate::pipe::Pipe
+!*/
+
+/// Composes two functions, right to left: `compose(f, g)(x)` is `f(g(x))`.
+///
+/// This matches the mathematical `f ∘ g`. See [`pipe_fn`] for the
+/// left-to-right order.
+///
+/// ## Examples
+///
+/// ```rust
+/// use wyz::func::compose;
+///
+/// let mut describe = compose(|n: usize| n.to_string(), str::len);
+/// assert_eq!(describe("hello"), "5");
+/// ```
+#[inline]
+pub fn compose<A, B, C>(
+	mut f: impl FnMut(B) -> C,
+	mut g: impl FnMut(A) -> B,
+) -> impl FnMut(A) -> C {
+	move |a| f(g(a))
+}
+
+/// Chains two functions, left to right: `pipe_fn(f, g)(x)` is `g(f(x))`.
+///
+/// Longer pipelines nest: `pipe_fn(f, pipe_fn(g, h))` runs `f`, then `g`, then
+/// `h`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use wyz::func::pipe_fn;
+///
+/// let mut parse_then_double = pipe_fn(
+///  |s: &str| s.parse::<i32>().unwrap_or(0),
+///  |n| n * 2,
+/// );
+/// assert_eq!(parse_then_double("21"), 42);
+/// ```
+#[inline]
+pub fn pipe_fn<A, B, C>(
+	mut f: impl FnMut(A) -> B,
+	mut g: impl FnMut(B) -> C,
+) -> impl FnMut(A) -> C {
+	move |a| g(f(a))
+}
+
+/// Swaps the arguments of a two-argument function.
+///
+/// ## Examples
+///
+/// ```rust
+/// use wyz::func::flip;
+///
+/// let mut sub_from = flip(|a: i32, b: i32| a - b);
+/// assert_eq!(sub_from(1, 10), 9);
+/// ```
+#[inline]
+pub fn flip<A, B, C>(mut func: impl FnMut(A, B) -> C) -> impl FnMut(B, A) -> C {
+	move |b, a| func(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+	use core::cmp::Ordering;
+
+	use super::*;
+
+	#[test]
+	fn composition() {
+		let mut f = compose(|x: i32| x + 1, |x: i32| x * 10);
+		assert_eq!(f(2), 21);
+		let mut g = pipe_fn(|x: i32| x + 1, |x: i32| x * 10);
+		assert_eq!(g(2), 30);
+
+		let mut h = pipe_fn(|x: u8| x + 1, pipe_fn(u32::from, |x| x << 8));
+		assert_eq!(h(0), 256);
+	}
+
+	#[test]
+	fn stateful() {
+		let mut calls = 0;
+		let counted = |x: i32| {
+			calls += 1;
+			x
+		};
+		let total: i32 = [1, 2, 3]
+			.iter()
+			.copied()
+			.map(pipe_fn(counted, |x| x * x))
+			.sum();
+		assert_eq!(total, 14);
+		assert_eq!(calls, 3);
+	}
+
+	#[test]
+	fn flipping() {
+		let mut cmp = flip(i32::cmp);
+		assert_eq!(cmp(&1, &2), Ordering::Greater);
+
+		let digits = [1, 2, 3]
+			.iter()
+			.rev()
+			.fold(0, flip(|d: &i32, acc: i32| acc * 10 + d));
+		assert_eq!(digits, 321);
+	}
+}
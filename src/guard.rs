@@ -0,0 +1,241 @@
+/*! Scope guards
+
+A scope guard runs a function when it goes out of scope. This is the idiomatic
+way to attach cleanup to a scope without writing a dedicated type with a `Drop`
+implementation, and it runs no matter how the scope exits: by falling off the
+end, by an early `return` or `?`, or by unwinding from a panic.
+
+The [`ScopeGuard`] type holds the function, and the `defer!` macro creates an
+anonymous guard that lives until the end of the enclosing scope. Guards run in
+the reverse order of their creation, just as local variables are dropped.
+
+With the `std` feature, `UnwindGuard` and the `defer_on_unwind!` macro run
+their function only when the scope is exited by a panic, which is useful for
+rolling back partially-completed work.
+
+# Examples
+
+```rust
+use std::cell::RefCell;
+use wyz::defer;
+
+let log = RefCell::new(Vec::new());
+{
+  defer!(log.borrow_mut().push("first defer"));
+  defer!(log.borrow_mut().push("second defer"));
+  log.borrow_mut().push("scope body");
+}
+assert_eq!(*log.borrow(), ["scope body", "second defer", "first defer"]);
+```
+!*/
+
+use core::mem::ManuallyDrop;
+
+/// `defer!` macro
+#[macro_export]
+macro_rules! defer {
+	( $( $body:tt )* ) => {
+		let _guard = $crate::guard::ScopeGuard::new(|| {
+			$( $body )*
+		});
+	};
+}
+
+/// `defer_on_unwind!` macro
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! defer_on_unwind {
+	( $( $body:tt )* ) => {
+		let _guard = $crate::guard::UnwindGuard::new(|| {
+			$( $body )*
+		});
+	};
+}
+
+/** Runs a function when it goes out of scope.
+
+The function can be prevented from running by calling `.dismiss()`.
+
+## Examples
+
+```rust
+use std::cell::Cell;
+use wyz::guard::ScopeGuard;
+
+let done = Cell::new(false);
+{
+  let _guard = ScopeGuard::new(|| done.set(true));
+  assert!(!done.get());
+}
+assert!(done.get());
+```
+**/
+#[must_use = "the guard runs its function immediately if it is not bound"]
+pub struct ScopeGuard<F>
+where F: FnOnce()
+{
+	/// The function to run. It is only ever taken out in `Drop` or `dismiss`.
+	func: ManuallyDrop<F>,
+}
+
+impl<F> ScopeGuard<F>
+where F: FnOnce()
+{
+	/// Creates a guard that runs `func` when it is dropped.
+	#[inline]
+	pub fn new(func: F) -> Self {
+		Self {
+			func: ManuallyDrop::new(func),
+		}
+	}
+
+	/// Destroys the guard without running its function, and returns the
+	/// function.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::guard::ScopeGuard;
+	///
+	/// let guard = ScopeGuard::new(|| panic!("never runs"));
+	/// drop(guard.dismiss());
+	/// ```
+	#[inline]
+	pub fn dismiss(self) -> F {
+		let mut this = ManuallyDrop::new(self);
+		//  SAFETY: `this` is never dropped, so `func` is not taken twice.
+		unsafe { ManuallyDrop::take(&mut this.func) }
+	}
+}
+
+impl<F> Drop for ScopeGuard<F>
+where F: FnOnce()
+{
+	#[inline]
+	fn drop(&mut self) {
+		//  SAFETY: this is the only place, other than `dismiss` (which
+		//  prevents this destructor from running), that takes `func`.
+		let func = unsafe { ManuallyDrop::take(&mut self.func) };
+		func();
+	}
+}
+
+/** Runs a function when it goes out of scope during a panic.
+
+When the scope exits normally, the function is dropped without being called.
+
+## Examples
+
+```rust
+use std::{cell::Cell, panic};
+use wyz::guard::UnwindGuard;
+
+let rolled_back = Cell::new(false);
+let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+  let _guard = UnwindGuard::new(|| rolled_back.set(true));
+  panic!("failed halfway through");
+}));
+assert!(res.is_err());
+assert!(rolled_back.get());
+```
+**/
+#[cfg(feature = "std")]
+#[must_use = "the guard is dropped immediately if it is not bound"]
+pub struct UnwindGuard<F>
+where F: FnOnce()
+{
+	/// The guard holding the function, which is dismissed on a normal exit.
+	inner: ManuallyDrop<ScopeGuard<F>>,
+}
+
+#[cfg(feature = "std")]
+impl<F> UnwindGuard<F>
+where F: FnOnce()
+{
+	/// Creates a guard that runs `func` if it is dropped during a panic.
+	#[inline]
+	pub fn new(func: F) -> Self {
+		Self {
+			inner: ManuallyDrop::new(ScopeGuard::new(func)),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<F> Drop for UnwindGuard<F>
+where F: FnOnce()
+{
+	#[inline]
+	fn drop(&mut self) {
+		//  SAFETY: this is the only place that takes `inner`.
+		let guard = unsafe { ManuallyDrop::take(&mut self.inner) };
+		if !std::thread::panicking() {
+			drop(guard.dismiss());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::cell::Cell;
+
+	use super::*;
+
+	#[test]
+	fn runs_on_exit() {
+		let count = Cell::new(0);
+		{
+			defer!(count.set(count.get() + 1));
+			defer! {
+				count.set(count.get() * 10);
+			}
+			count.set(5);
+		}
+		assert_eq!(count.get(), 51);
+	}
+
+	#[test]
+	fn early_return() {
+		let count = Cell::new(0);
+		let run = |stop: bool| {
+			defer!(count.set(count.get() + 1));
+			if stop {
+				return;
+			}
+			count.set(count.get() + 10);
+		};
+		run(true);
+		assert_eq!(count.get(), 1);
+		run(false);
+		assert_eq!(count.get(), 12);
+	}
+
+	#[test]
+	fn dismissal() {
+		let count = Cell::new(0);
+		let guard = ScopeGuard::new(|| count.set(1));
+		let func = guard.dismiss();
+		assert_eq!(count.get(), 0);
+		func();
+		assert_eq!(count.get(), 1);
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn unwinding() {
+		use std::panic;
+
+		let count = Cell::new(0);
+		{
+			defer_on_unwind!(count.set(1));
+		}
+		assert_eq!(count.get(), 0);
+
+		let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+			defer_on_unwind!(count.set(2));
+			panic!("unwind");
+		}));
+		assert!(res.is_err());
+		assert_eq!(count.get(), 2);
+	}
+}
@@ -0,0 +1,301 @@
+/*! Memory layout computation
+
+Container authors computing allocation sizes need to lay out arrays and structs
+of dynamically-chosen element layouts. The standard library’s `Layout::repeat`,
+`Layout::repeat_packed`, `Layout::extend_packed`, and
+`Layout::padding_needed_for` are not stable. The [`LayoutExt`] trait provides
+them, and [`StructLayout`] accumulates field offsets for a `#[repr(C)]`-style
+record. Failures are reported as a [`LayoutExtError`], rather than the opaque
+`LayoutError`.
+
+# Examples
+
+```rust
+use core::alloc::Layout;
+use wyz::layout::StructLayout;
+
+// A header followed by a dynamically-sized array of `u32`s.
+let mut record = StructLayout::new();
+let len_at = record.field(Layout::new::<u8>()).unwrap();
+let data_at = record.field(Layout::array::<u32>(3).unwrap()).unwrap();
+let layout = record.finish();
+
+assert_eq!((len_at, data_at), (0, 4));
+assert_eq!((layout.size(), layout.align()), (16, 4));
+```
+!*/
+
+use core::{
+	alloc::Layout,
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
+};
+
+use crate::align;
+
+/// Layout computations that are not yet stable in the standard library.
+pub trait LayoutExt: Sized {
+	/// Computes the padding needed after a value of this layout so that a
+	/// value with alignment `align` can follow it.
+	///
+	/// ## Panics
+	///
+	/// This panics if `align` is not a power of two.
+	fn padding_to(&self, align: usize) -> usize;
+
+	/// Lays out `count` values of this layout as an array, with each element
+	/// padded to the layout’s alignment.
+	///
+	/// ## Returns
+	///
+	/// The layout of the array, and the distance between the starts of
+	/// successive elements.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use core::alloc::Layout;
+	/// use wyz::layout::LayoutExt;
+	///
+	/// let elem = Layout::from_size_align(6, 4).unwrap();
+	/// let (array, stride) = elem.repeat_array(3).unwrap();
+	/// assert_eq!(stride, 8);
+	/// assert_eq!(array.size(), 24);
+	/// ```
+	fn repeat_array(
+		&self,
+		count: usize,
+	) -> Result<(Self, usize), LayoutExtError>;
+
+	/// Lays out `count` values of this layout with no padding between them.
+	///
+	/// The resulting layout keeps this layout’s alignment, so only the first
+	/// element is guaranteed to be aligned.
+	fn repeat_packed_array(&self, count: usize) -> Result<Self, LayoutExtError>;
+
+	/// Appends `next` to this layout with no padding between them.
+	///
+	/// The resulting layout keeps this layout’s alignment, so `next` is not
+	/// guaranteed to be aligned.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use core::alloc::Layout;
+	/// use wyz::layout::LayoutExt;
+	///
+	/// let packed = Layout::new::<u8>()
+	///  .extend_packed_layout(Layout::new::<u32>())
+	///  .unwrap();
+	/// assert_eq!((packed.size(), packed.align()), (5, 1));
+	/// ```
+	fn extend_packed_layout(&self, next: Self) -> Result<Self, LayoutExtError>;
+
+	/// Changes the alignment of the layout, keeping its size.
+	///
+	/// Unlike `Layout::align_to`, this reports an alignment that is not a
+	/// power of two separately from a size that overflows.
+	fn with_align(&self, align: usize) -> Result<Self, LayoutExtError>;
+}
+
+impl LayoutExt for Layout {
+	#[inline]
+	fn padding_to(&self, align: usize) -> usize {
+		align::padding_needed_for(self.size(), align)
+	}
+
+	#[inline]
+	fn repeat_array(
+		&self,
+		count: usize,
+	) -> Result<(Self, usize), LayoutExtError> {
+		let stride = self.pad_to_align().size();
+		let size = stride
+			.checked_mul(count)
+			.ok_or(LayoutExtError::SizeOverflow)?;
+		let layout = Layout::from_size_align(size, self.align())?;
+		Ok((layout, stride))
+	}
+
+	#[inline]
+	fn repeat_packed_array(&self, count: usize) -> Result<Self, LayoutExtError> {
+		let size = self
+			.size()
+			.checked_mul(count)
+			.ok_or(LayoutExtError::SizeOverflow)?;
+		Ok(Layout::from_size_align(size, self.align())?)
+	}
+
+	#[inline]
+	fn extend_packed_layout(&self, next: Self) -> Result<Self, LayoutExtError> {
+		let size = self
+			.size()
+			.checked_add(next.size())
+			.ok_or(LayoutExtError::SizeOverflow)?;
+		Ok(Layout::from_size_align(size, self.align())?)
+	}
+
+	#[inline]
+	fn with_align(&self, align: usize) -> Result<Self, LayoutExtError> {
+		if !align.is_power_of_two() {
+			return Err(LayoutExtError::InvalidAlign(align));
+		}
+		Ok(Layout::from_size_align(self.size(), align)?)
+	}
+}
+
+/** Accumulates the fields of a `#[repr(C)]`-style record.
+
+Each field is placed at the next offset that satisfies its alignment, and the
+record’s alignment is the largest of its fields’ alignments. `.finish()` pads
+the record’s size to a multiple of its alignment.
+**/
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StructLayout {
+	/// The layout of the fields placed so far, without trailing padding.
+	layout: Layout,
+}
+
+impl StructLayout {
+	/// Begins an empty record.
+	#[inline]
+	pub fn new() -> Self {
+		Self {
+			layout: Layout::new::<()>(),
+		}
+	}
+
+	/// Places a field with the given layout.
+	///
+	/// ## Returns
+	///
+	/// The offset of the field from the start of the record.
+	#[inline]
+	pub fn field(&mut self, field: Layout) -> Result<usize, LayoutExtError> {
+		let (layout, offset) = self.layout.extend(field)?;
+		self.layout = layout;
+		Ok(offset)
+	}
+
+	/// Places a field of type `T`.
+	///
+	/// ## Returns
+	///
+	/// The offset of the field from the start of the record.
+	#[inline]
+	pub fn field_of<T>(&mut self) -> Result<usize, LayoutExtError> {
+		self.field(Layout::new::<T>())
+	}
+
+	/// Finishes the record, padding its size to a multiple of its alignment.
+	#[inline]
+	pub fn finish(self) -> Layout {
+		self.layout.pad_to_align()
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl Default for StructLayout {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Describes why a layout could not be computed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LayoutExtError {
+	/// The size, rounded up to the alignment, would exceed `isize::MAX`.
+	SizeOverflow,
+	/// The alignment is not a power of two.
+	InvalidAlign(usize),
+}
+
+impl From<core::alloc::LayoutError> for LayoutExtError {
+	/// The only layout errors that can reach this conversion are overflows;
+	/// alignments are validated before constructing a layout.
+	#[inline]
+	fn from(_: core::alloc::LayoutError) -> Self {
+		Self::SizeOverflow
+	}
+}
+
+impl Display for LayoutExtError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::SizeOverflow => fmt.write_str("layout size overflows `isize`"),
+			Self::InvalidAlign(align) => {
+				write!(fmt, "alignment {} is not a power of two", align)
+			},
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LayoutExtError {
+}
+
+#[cfg(test)]
+mod tests {
+	use core::mem;
+
+	use super::*;
+
+	#[repr(C)]
+	struct Record {
+		tag: u8,
+		value: u64,
+		flag: u16,
+	}
+
+	#[test]
+	fn records() {
+		let mut record = StructLayout::default();
+		assert_eq!(record.field_of::<u8>(), Ok(0));
+		assert_eq!(record.field_of::<u64>(), Ok(mem::align_of::<u64>()));
+		let flag = record.field_of::<u16>().unwrap();
+		assert_eq!(record.finish(), Layout::new::<Record>());
+		assert_eq!(flag, mem::size_of::<u64>() + mem::align_of::<u64>());
+
+		let mut huge = StructLayout::new();
+		huge.field(Layout::from_size_align(isize::MAX as usize, 1).unwrap())
+			.unwrap();
+		assert_eq!(
+			huge.field(Layout::new::<u16>()),
+			Err(LayoutExtError::SizeOverflow),
+		);
+	}
+
+	#[test]
+	fn arrays() {
+		let elem = Layout::new::<u32>();
+		assert_eq!(elem.repeat_array(4), Ok((Layout::new::<[u32; 4]>(), 4)));
+		assert_eq!(elem.repeat_packed_array(4), Ok(Layout::new::<[u32; 4]>()));
+		assert_eq!(
+			elem.repeat_array(usize::MAX),
+			Err(LayoutExtError::SizeOverflow)
+		);
+
+		let odd = Layout::from_size_align(3, 2).unwrap();
+		assert_eq!(odd.padding_to(2), 1);
+		assert_eq!(odd.repeat_array(2).unwrap().0.size(), 8);
+		assert_eq!(odd.repeat_packed_array(2).unwrap().size(), 6);
+		assert_eq!(odd.extend_packed_layout(odd).unwrap().size(), 6);
+	}
+
+	#[test]
+	fn realign() {
+		let layout = Layout::new::<u8>();
+		assert_eq!(layout.with_align(16).unwrap().align(), 16);
+		assert_eq!(layout.with_align(3), Err(LayoutExtError::InvalidAlign(3)));
+		assert_eq!(
+			Layout::from_size_align(isize::MAX as usize, 1)
+				.unwrap()
+				.with_align(2),
+			Err(LayoutExtError::SizeOverflow),
+		);
+	}
+}
@@ -17,19 +17,66 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "tap")]
+pub extern crate tap;
+
+pub mod align;
 pub mod bidi;
+pub mod bytes;
+pub mod checked;
+pub mod combinator;
+pub mod endian;
+pub mod env;
 pub mod fmt;
+pub mod func;
+pub mod layout;
+pub mod marker;
+pub mod nonzero;
+pub mod once;
+pub mod pipe;
 pub mod range;
+pub mod recycle;
+pub mod slice;
+pub mod text;
+pub mod typestate;
+
+#[cfg(feature = "metadata")]
+#[clippy::msrv = "1.79"]
+pub mod metadata;
+
+#[macro_use]
+pub mod guard;
+
+#[macro_use]
+pub mod sealed;
+
+#[macro_use]
+pub mod delegate;
+
+#[macro_use]
+pub mod error;
 
-#[cfg(feature = "std")]
 #[macro_use]
 pub mod exit;
 
 pub use self::{
+	align::AlignExt,
 	bidi::*,
+	bytes::BytesOf,
+	checked::CheckedExt,
+	combinator::{
+		FlattenExt,
+		OptionExt,
+		ResultExt,
+	},
+	exit::{
+		ExitStatus,
+		OrExit,
+	},
 	fmt::*,
+	layout::LayoutExt,
+	nonzero::NonZeroExt,
 	range::*,
+	slice::SliceExt,
+	text::StrExt,
 };
-
-#[cfg(feature = "std")]
-pub use self::exit::*;
@@ -0,0 +1,239 @@
+/*! Variance and auto-trait markers
+
+Generic types that do not directly own a value of their type parameter need a
+`PhantomData` field to tell the compiler how they relate to it. The correct
+`PhantomData` spelling for each combination of variance and auto-traits is easy
+to get wrong, and does not say what it means when read later.
+
+This module provides named, zero-sized, markers instead. The variance markers do
+not affect whether their container is `Send`, `Sync`, or `Unpin`, and the
+auto-trait markers do not affect variance.
+
+| Marker               | Spelling                     | Effect                    |
+| -------------------- | ---------------------------- | ------------------------- |
+| `Covariant<T>`       | `PhantomData<fn() -> T>`     | covariant in `T`          |
+| `Contravariant<T>`   | `PhantomData<fn(T)>`         | contravariant in `T`      |
+| `Invariant<T>`       | `PhantomData<fn(T) -> T>`    | invariant in `T`          |
+| `NotSend`            | `PhantomData<*mut ()>`       | `!Send`, but still `Sync` |
+| `NotSync`            | `PhantomData<Cell<()>>`      | `!Sync`, but still `Send` |
+| `NotUnpin`           | `PhantomPinned`              | `!Unpin`                  |
+
+# Examples
+
+```rust
+use wyz::marker::{Invariant, NotSync};
+
+/// A handle that must not be shared between threads, and whose `T` must not
+/// be changed by subtyping.
+struct Handle<T> {
+  id: usize,
+  _ty: Invariant<T>,
+  _sync: NotSync,
+}
+```
+!*/
+
+use core::{
+	cell::Cell,
+	cmp::Ordering,
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	hash::{
+		Hash,
+		Hasher,
+	},
+	marker::{
+		PhantomData,
+		PhantomPinned,
+	},
+};
+
+macro_rules! variance {
+	( $( $(#[$attr:meta])* $name:ident => $phantom:ty );+ $(;)? ) => { $(
+		$(#[$attr])*
+		pub struct $name<T> {
+			/// The variance marker.
+			_ty: PhantomData<$phantom>,
+		}
+
+		impl<T> $name<T> {
+			/// Creates the marker.
+			#[inline(always)]
+			pub const fn new() -> Self {
+				Self { _ty: PhantomData }
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl<T> Clone for $name<T> {
+			#[inline(always)]
+			fn clone(&self) -> Self {
+				*self
+			}
+		}
+
+		impl<T> Copy for $name<T> {}
+
+		#[cfg(not(tarpaulin_include))]
+		impl<T> Debug for $name<T> {
+			#[inline(always)]
+			fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+				fmt.write_str(stringify!($name))
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl<T> Default for $name<T> {
+			#[inline(always)]
+			fn default() -> Self {
+				Self::new()
+			}
+		}
+
+		impl<T> Eq for $name<T> {}
+
+		#[cfg(not(tarpaulin_include))]
+		impl<T> Hash for $name<T> {
+			#[inline(always)]
+			fn hash<H>(&self, _: &mut H)
+			where H: Hasher {
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl<T> Ord for $name<T> {
+			#[inline(always)]
+			fn cmp(&self, _: &Self) -> Ordering {
+				Ordering::Equal
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl<T> PartialEq for $name<T> {
+			#[inline(always)]
+			fn eq(&self, _: &Self) -> bool {
+				true
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl<T> PartialOrd for $name<T> {
+			#[inline(always)]
+			fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+				Some(self.cmp(other))
+			}
+		}
+	)+ };
+}
+
+variance! {
+	/// Makes its container covariant in `T`: a `Foo<&'static U>` can be used
+	/// where a `Foo<&'a U>` is expected.
+	Covariant => fn() -> T;
+	/// Makes its container contravariant in `T`: a `Foo<&'a U>` can be used
+	/// where a `Foo<&'static U>` is expected.
+	Contravariant => fn(T);
+	/// Makes its container invariant in `T`: it cannot be changed by
+	/// subtyping at all.
+	Invariant => fn(T) -> T;
+}
+
+macro_rules! auto_trait {
+	( $( $(#[$attr:meta])* $name:ident => $phantom:ty );+ $(;)? ) => { $(
+		$(#[$attr])*
+		#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+		pub struct $name {
+			/// The auto-trait marker.
+			_marker: $phantom,
+		}
+
+		impl $name {
+			/// Creates the marker.
+			#[inline(always)]
+			pub const fn new() -> Self {
+				Self { _marker: PhantomData }
+			}
+		}
+	)+ };
+}
+
+auto_trait! {
+	/// Prevents its container from implementing `Send`.
+	NotSend => PhantomData<*mut ()>;
+	/// Prevents its container from implementing `Sync`.
+	NotSync => PhantomData<Cell<()>>;
+}
+
+//  SAFETY: `NotSend` carries no data, and only opts out of `Send`.
+unsafe impl Sync for NotSend {
+}
+
+/// Prevents its container from implementing `Unpin`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct NotUnpin {
+	/// The auto-trait marker.
+	_marker: PhantomPinned,
+}
+
+impl NotUnpin {
+	/// Creates the marker.
+	#[inline(always)]
+	pub const fn new() -> Self {
+		Self {
+			_marker: PhantomPinned,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::{
+		cell::Cell,
+		mem,
+	};
+
+	use super::*;
+
+	fn send<T: Send>() {
+	}
+
+	fn sync<T: Sync>() {
+	}
+
+	fn unpin<T: Unpin>() {
+	}
+
+	#[test]
+	fn auto_traits() {
+		//  Variance markers do not depend on their parameter’s auto-traits.
+		send::<Invariant<Cell<*mut ()>>>();
+		sync::<Covariant<Cell<*mut ()>>>();
+		unpin::<Contravariant<PhantomPinned>>();
+
+		sync::<NotSend>();
+		send::<NotSync>();
+		send::<NotUnpin>();
+		sync::<NotUnpin>();
+
+		assert_eq!(mem::size_of::<Invariant<u64>>(), 0);
+		assert_eq!(mem::size_of::<NotSend>(), 0);
+		assert_eq!(NotSync::new(), NotSync::default());
+	}
+
+	#[test]
+	fn variance() {
+		fn shorten<'a>(long: Covariant<&'static str>) -> Covariant<&'a str> {
+			long
+		}
+
+		fn lengthen(short: Contravariant<&str>) -> Contravariant<&'static str> {
+			short
+		}
+
+		assert_eq!(shorten(Covariant::new()), Covariant::new());
+		assert_eq!(lengthen(Contravariant::new()), Contravariant::new());
+	}
+}
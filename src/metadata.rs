@@ -0,0 +1,206 @@
+/*! Pointer metadata
+
+A pointer to a dynamically-sized type carries metadata alongside its address:
+the element count of a slice or string. The standard library’s `ptr_metadata`
+API, which splits a pointer into its address and metadata and joins them back
+together, is not stable. This module approximates it for the pointee types whose
+metadata can be handled on stable Rust: sized types, whose metadata is `()`,
+and slices and `str`, whose metadata is their length.
+
+Reading the length of a slice pointer without dereferencing it requires Rust
+1.79, so this module is only available with the `metadata` feature.
+
+# Examples
+
+```rust
+use wyz::metadata;
+
+let data = [1u16, 2, 3, 4];
+let ptr: *const [u16] = &data[1 ..];
+
+let (addr, len) = metadata::to_raw_parts(ptr);
+assert_eq!(len, 3);
+
+let rebuilt: *const [u16] = metadata::from_raw_parts(addr, len - 1);
+assert_eq!(unsafe { &*rebuilt }, &[2, 3]);
+```
+!*/
+
+use core::{
+	fmt::Debug,
+	hash::Hash,
+	ptr,
+};
+
+/// A pointee type whose pointers can be split into an address and metadata.
+pub trait Metadata: seal::Sealed {
+	/// The metadata carried by pointers to this type.
+	type Meta: Copy + Debug + Eq + Hash + Ord + Send + Sync + Unpin;
+
+	/// Reads the metadata of a pointer. The pointer is not dereferenced.
+	fn metadata(ptr: *const Self) -> Self::Meta;
+
+	/// Builds a pointer from an address and metadata.
+	fn from_raw_parts(data: *const (), meta: Self::Meta) -> *const Self;
+
+	/// Builds a mutable pointer from an address and metadata.
+	fn from_raw_parts_mut(data: *mut (), meta: Self::Meta) -> *mut Self;
+}
+
+impl<T> Metadata for T {
+	type Meta = ();
+
+	#[inline(always)]
+	#[cfg(not(tarpaulin_include))]
+	fn metadata(_: *const Self) -> Self::Meta {
+	}
+
+	#[inline(always)]
+	#[cfg(not(tarpaulin_include))]
+	fn from_raw_parts(data: *const (), (): Self::Meta) -> *const Self {
+		data.cast::<T>()
+	}
+
+	#[inline(always)]
+	#[cfg(not(tarpaulin_include))]
+	fn from_raw_parts_mut(data: *mut (), (): Self::Meta) -> *mut Self {
+		data.cast::<T>()
+	}
+}
+
+impl<T> Metadata for [T] {
+	type Meta = usize;
+
+	#[inline]
+	fn metadata(ptr: *const Self) -> Self::Meta {
+		ptr.len()
+	}
+
+	#[inline]
+	fn from_raw_parts(data: *const (), meta: Self::Meta) -> *const Self {
+		ptr::slice_from_raw_parts(data.cast::<T>(), meta)
+	}
+
+	#[inline]
+	fn from_raw_parts_mut(data: *mut (), meta: Self::Meta) -> *mut Self {
+		ptr::slice_from_raw_parts_mut(data.cast::<T>(), meta)
+	}
+}
+
+impl Metadata for str {
+	type Meta = usize;
+
+	#[inline]
+	fn metadata(ptr: *const Self) -> Self::Meta {
+		(ptr as *const [u8]).len()
+	}
+
+	#[inline]
+	fn from_raw_parts(data: *const (), meta: Self::Meta) -> *const Self {
+		<[u8]>::from_raw_parts(data, meta) as *const Self
+	}
+
+	#[inline]
+	fn from_raw_parts_mut(data: *mut (), meta: Self::Meta) -> *mut Self {
+		<[u8]>::from_raw_parts_mut(data, meta) as *mut Self
+	}
+}
+
+/// Reads the metadata of a pointer, without dereferencing it.
+///
+/// ## Examples
+///
+/// ```rust
+/// use wyz::metadata;
+///
+/// assert_eq!(metadata::metadata("hello" as *const str), 5);
+/// assert_eq!(metadata::metadata(&5u8 as *const u8), ());
+/// ```
+#[inline]
+pub fn metadata<T>(ptr: *const T) -> T::Meta
+where T: ?Sized + Metadata {
+	T::metadata(ptr)
+}
+
+/// Splits a pointer into its address and its metadata.
+#[inline]
+pub fn to_raw_parts<T>(ptr: *const T) -> (*const (), T::Meta)
+where T: ?Sized + Metadata {
+	(ptr.cast::<()>(), T::metadata(ptr))
+}
+
+/// Splits a mutable pointer into its address and its metadata.
+#[inline]
+pub fn to_raw_parts_mut<T>(ptr: *mut T) -> (*mut (), T::Meta)
+where T: ?Sized + Metadata {
+	(ptr.cast::<()>(), T::metadata(ptr))
+}
+
+/// Builds a pointer from an address and metadata.
+///
+/// This is always safe, as it does not dereference the pointer. Using the
+/// pointer is only sound if the metadata describes the value at the address.
+#[inline]
+pub fn from_raw_parts<T>(data: *const (), meta: T::Meta) -> *const T
+where T: ?Sized + Metadata {
+	T::from_raw_parts(data, meta)
+}
+
+/// Builds a mutable pointer from an address and metadata.
+///
+/// This is always safe, as it does not dereference the pointer. Using the
+/// pointer is only sound if the metadata describes the value at the address.
+#[inline]
+pub fn from_raw_parts_mut<T>(data: *mut (), meta: T::Meta) -> *mut T
+where T: ?Sized + Metadata {
+	T::from_raw_parts_mut(data, meta)
+}
+
+crate::sealed! {
+	mod seal;
+	str,
+	impl<T> T,
+	impl<T> [T],
+}
+
+#[cfg(test)]
+mod tests {
+	use core::ptr::NonNull;
+
+	use super::*;
+
+	#[test]
+	fn sized() {
+		let mut num = 5i32;
+		let (addr, ()) = to_raw_parts_mut(&mut num as *mut i32);
+		unsafe {
+			*from_raw_parts_mut::<i32>(addr, ()) += 1;
+		}
+		assert_eq!(num, 6);
+	}
+
+	#[test]
+	fn slices() {
+		let mut data = [1u32, 2, 3, 4, 5];
+		let (addr, len) = to_raw_parts_mut(&mut data[.. 4] as *mut [u32]);
+		assert_eq!((addr as usize, len), (data.as_ptr() as usize, 4));
+
+		let tail = from_raw_parts_mut::<[u32]>(addr, 2);
+		unsafe { &mut *tail }.fill(0);
+		assert_eq!(data, [0, 0, 3, 4, 5]);
+
+		//  Null and dangling addresses are never read.
+		let null = ptr::slice_from_raw_parts(ptr::null::<u64>(), 7);
+		assert_eq!(to_raw_parts(null), (ptr::null(), 7));
+		let dangling = NonNull::<u8>::dangling().as_ptr().cast::<()>();
+		assert_eq!(metadata(from_raw_parts::<[u8]>(dangling, !0)), !0);
+	}
+
+	#[test]
+	fn strings() {
+		let text = "hello, world";
+		let (addr, len) = to_raw_parts(text as *const str);
+		assert_eq!(len, 12);
+		assert_eq!(unsafe { &*from_raw_parts::<str>(addr, 5) }, "hello");
+	}
+}
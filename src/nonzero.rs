@@ -0,0 +1,241 @@
+/*! `NonZero` integer helpers
+
+The `NonZero*` integer types are useful for enabling niche optimizations and
+for documenting that a value can never be zero, but working with them requires
+constant unwrapping with `.get()` and rewrapping with `::new()`. The
+[`NonZeroExt`] trait provides arithmetic, mapping, comparison, and width
+conversion that stay within the non-zero types.
+
+# Examples
+
+```rust
+use core::num::{NonZeroU8, NonZeroU32};
+use wyz::nonzero::NonZeroExt;
+
+let count = NonZeroU8::new_saturating(0);
+assert!(count.eq_prim(1));
+
+let doubled = count.try_mul(2).unwrap();
+let wide: NonZeroU32 = doubled.try_cast().unwrap();
+assert_eq!(wide.get(), 2);
+```
+!*/
+
+use core::{
+	cmp::Ordering,
+	convert::TryFrom,
+	num::{
+		NonZeroI8,
+		NonZeroI16,
+		NonZeroI32,
+		NonZeroI64,
+		NonZeroI128,
+		NonZeroIsize,
+		NonZeroU8,
+		NonZeroU16,
+		NonZeroU32,
+		NonZeroU64,
+		NonZeroU128,
+		NonZeroUsize,
+	},
+};
+
+/// Extension methods for the `NonZero*` integers.
+pub trait NonZeroExt: seal::Sealed + Copy + Sized {
+	/// The primitive integer that this type wraps.
+	type Prim: Copy + Ord;
+
+	/// Wraps a primitive, replacing zero with one.
+	fn new_saturating(value: Self::Prim) -> Self;
+
+	/// Adds a primitive, producing `None` if the sum overflows or is zero.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use core::num::NonZeroI8;
+	/// use wyz::nonzero::NonZeroExt;
+	///
+	/// let one = NonZeroI8::new(1).unwrap();
+	/// assert_eq!(one.try_add(2).map(NonZeroI8::get), Some(3));
+	/// assert!(one.try_add(-1).is_none());
+	/// assert!(one.try_add(i8::MAX).is_none());
+	/// ```
+	fn try_add(self, rhs: Self::Prim) -> Option<Self>;
+
+	/// Subtracts a primitive, producing `None` if the difference overflows
+	/// or is zero.
+	fn try_sub(self, rhs: Self::Prim) -> Option<Self>;
+
+	/// Multiplies by a primitive, producing `None` if the product overflows
+	/// or is zero.
+	fn try_mul(self, rhs: Self::Prim) -> Option<Self>;
+
+	/// Applies a function to the wrapped primitive, producing `None` if the
+	/// function returns zero.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use core::num::NonZeroU16;
+	/// use wyz::nonzero::NonZeroExt;
+	///
+	/// let n = NonZeroU16::new(6).unwrap();
+	/// assert_eq!(n.map(|x| x / 2).map(NonZeroU16::get), Some(3));
+	/// assert!(n.map(|x| x / 8).is_none());
+	/// ```
+	fn map(self, func: impl FnOnce(Self::Prim) -> Self::Prim) -> Option<Self>;
+
+	/// Tests whether the wrapped primitive is equal to `other`.
+	fn eq_prim(self, other: Self::Prim) -> bool;
+
+	/// Compares the wrapped primitive to `other`.
+	fn cmp_prim(self, other: Self::Prim) -> Ordering;
+
+	/// Converts to a `NonZero` integer of another width or signedness,
+	/// producing `None` if the value does not fit.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use core::num::{NonZeroI8, NonZeroU16};
+	/// use wyz::nonzero::NonZeroExt;
+	///
+	/// let n = NonZeroU16::new(300).unwrap();
+	/// assert!(n.try_cast::<NonZeroI8>().is_none());
+	/// assert_eq!(n.try_cast::<NonZeroU16>(), Some(n));
+	/// ```
+	fn try_cast<U>(self) -> Option<U>
+	where
+		U: NonZeroExt + TryFrom<U::Prim>,
+		U::Prim: TryFrom<Self::Prim>;
+}
+
+macro_rules! nonzero {
+	($($nz:ident => $prim:ident),+ $(,)?) => { $(
+		impl NonZeroExt for $nz {
+			type Prim = $prim;
+
+			#[inline]
+			fn new_saturating(value: $prim) -> Self {
+				Self::new(value).unwrap_or_else(|| {
+					Self::new(1).expect("one is not zero")
+				})
+			}
+
+			#[inline]
+			fn try_add(self, rhs: $prim) -> Option<Self> {
+				self.get().checked_add(rhs).and_then(Self::new)
+			}
+
+			#[inline]
+			fn try_sub(self, rhs: $prim) -> Option<Self> {
+				self.get().checked_sub(rhs).and_then(Self::new)
+			}
+
+			#[inline]
+			fn try_mul(self, rhs: $prim) -> Option<Self> {
+				self.get().checked_mul(rhs).and_then(Self::new)
+			}
+
+			#[inline]
+			fn map(self, func: impl FnOnce($prim) -> $prim) -> Option<Self> {
+				Self::new(func(self.get()))
+			}
+
+			#[inline(always)]
+			fn eq_prim(self, other: $prim) -> bool {
+				self.get() == other
+			}
+
+			#[inline(always)]
+			fn cmp_prim(self, other: $prim) -> Ordering {
+				self.get().cmp(&other)
+			}
+
+			#[inline]
+			fn try_cast<U>(self) -> Option<U>
+			where
+				U: NonZeroExt + TryFrom<U::Prim>,
+				U::Prim: TryFrom<$prim>,
+			{
+				let prim = U::Prim::try_from(self.get()).ok()?;
+				U::try_from(prim).ok()
+			}
+		}
+	)+ };
+}
+
+nonzero!(
+	NonZeroU8 => u8,
+	NonZeroU16 => u16,
+	NonZeroU32 => u32,
+	NonZeroU64 => u64,
+	NonZeroU128 => u128,
+	NonZeroUsize => usize,
+	NonZeroI8 => i8,
+	NonZeroI16 => i16,
+	NonZeroI32 => i32,
+	NonZeroI64 => i64,
+	NonZeroI128 => i128,
+	NonZeroIsize => isize,
+);
+
+crate::sealed! {
+	mod seal;
+	NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+	NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn construction() {
+		assert_eq!(NonZeroU8::new_saturating(0).get(), 1);
+		assert_eq!(NonZeroI32::new_saturating(-5).get(), -5);
+	}
+
+	#[test]
+	fn arithmetic() {
+		let n = NonZeroU8::new(10).unwrap();
+		assert!(n.try_add(5).unwrap().eq_prim(15));
+		assert!(n.try_add(250).is_none());
+		assert!(n.try_sub(3).unwrap().eq_prim(7));
+		assert!(n.try_sub(10).is_none());
+		assert!(n.try_sub(11).is_none());
+		assert!(n.try_mul(25).unwrap().eq_prim(250));
+		assert!(n.try_mul(0).is_none());
+		assert!(n.try_mul(26).is_none());
+
+		let m = NonZeroI64::new(-4).unwrap();
+		assert!(m.try_add(4).is_none());
+		assert!(m.try_mul(-2).unwrap().eq_prim(8));
+		assert!(m.map(i64::abs).unwrap().eq_prim(4));
+	}
+
+	#[test]
+	fn comparison() {
+		let n = NonZeroUsize::new(5).unwrap();
+		assert!(n.eq_prim(5));
+		assert!(!n.eq_prim(6));
+		assert_eq!(n.cmp_prim(4), Ordering::Greater);
+		assert_eq!(n.cmp_prim(6), Ordering::Less);
+	}
+
+	#[test]
+	fn casting() {
+		let n = NonZeroI16::new(-1).unwrap();
+		assert!(n.try_cast::<NonZeroU64>().is_none());
+		assert_eq!(n.try_cast::<NonZeroI128>().map(NonZeroI128::get), Some(-1));
+
+		let big = NonZeroU128::new(u128::MAX).unwrap();
+		assert!(big.try_cast::<NonZeroU64>().is_none());
+		assert!(big.try_cast::<NonZeroUsize>().is_none());
+		assert_eq!(
+			NonZeroU32::new(7).unwrap().try_cast::<NonZeroU8>(),
+			NonZeroU8::new(7),
+		);
+	}
+}
@@ -0,0 +1,438 @@
+/*! One-time initialization cells
+
+These cells start out empty, and can be written exactly once. After that, they
+hand out shared references to their value for the rest of their life. They
+mirror the standard library’s `OnceCell` and `OnceLock`, but are available in
+`#![no_std]` environments and in older compilers.
+
+- [`OnceCell`] is unsynchronized, and can only be used from one thread. It is
+  the cheapest option, and is suitable for firmware with no preemption.
+- [`AtomicOnceCell`] is synchronized with atomics, and can be shared between
+  threads or interrupt handlers. A thread that observes another thread in the
+  middle of initializing the cell spins until the value is ready, so it should
+  not be used where the initializer can be preempted by a reader on the same
+  core.
+
+# Examples
+
+```rust
+use wyz::once::AtomicOnceCell;
+
+static CONFIG: AtomicOnceCell<u32> = AtomicOnceCell::new();
+
+assert!(CONFIG.get().is_none());
+assert_eq!(*CONFIG.get_or_init(|| 115_200), 115_200);
+assert_eq!(CONFIG.set(9_600), Err(9_600));
+assert_eq!(CONFIG.get(), Some(&115_200));
+```
+!*/
+
+use core::{
+	cell::UnsafeCell,
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	hint,
+	mem::{
+		self,
+		MaybeUninit,
+	},
+	sync::atomic::{
+		AtomicU8,
+		Ordering,
+	},
+};
+
+use crate::guard::ScopeGuard;
+
+/** An unsynchronized cell that can be written once.
+
+This type is `!Sync`, so it cannot be shared between threads. Use
+[`AtomicOnceCell`] for that.
+
+## Examples
+
+```rust
+use wyz::once::OnceCell;
+
+let cell = OnceCell::new();
+assert!(cell.get().is_none());
+
+assert_eq!(cell.set(5), Ok(()));
+assert_eq!(cell.set(6), Err(6));
+assert_eq!(cell.get_or_init(|| 7), &5);
+```
+**/
+pub struct OnceCell<T> {
+	/// The value, which is `None` until the cell is written.
+	inner: UnsafeCell<Option<T>>,
+}
+
+impl<T> OnceCell<T> {
+	/// Creates a new, empty, cell.
+	#[inline]
+	pub const fn new() -> Self {
+		Self {
+			inner: UnsafeCell::new(None),
+		}
+	}
+
+	/// Gets a reference to the value, if the cell has been written.
+	#[inline]
+	pub fn get(&self) -> Option<&T> {
+		//  SAFETY: once written, the value is never modified through `&self`.
+		unsafe { &*self.inner.get() }.as_ref()
+	}
+
+	/// Gets a mutable reference to the value, if the cell has been written.
+	#[inline]
+	pub fn get_mut(&mut self) -> Option<&mut T> {
+		self.inner.get_mut().as_mut()
+	}
+
+	/// Writes a value into the cell, if it is empty.
+	///
+	/// ## Returns
+	///
+	/// `Ok(())` if the cell was empty, or `Err(value)` if it was already
+	/// written.
+	#[inline]
+	pub fn set(&self, value: T) -> Result<(), T> {
+		if self.get().is_some() {
+			return Err(value);
+		}
+		//  SAFETY: the cell is empty, so there are no outstanding references to
+		//  its value, and it is `!Sync`, so no other thread can observe it.
+		unsafe {
+			*self.inner.get() = Some(value);
+		}
+		Ok(())
+	}
+
+	/// Gets the value, initializing it with `func` if the cell is empty.
+	///
+	/// ## Panics
+	///
+	/// This panics if `func` initializes the cell itself, and propagates any
+	/// panic from `func`. The cell remains empty if `func` panics.
+	#[inline]
+	pub fn get_or_init(&self, func: impl FnOnce() -> T) -> &T {
+		if let Some(value) = self.get() {
+			return value;
+		}
+		let value = func();
+		assert!(self.set(value).is_ok(), "reentrant initialization");
+		self.get().expect("the cell was just written")
+	}
+
+	/// Takes the value out of the cell, leaving it empty.
+	#[inline]
+	pub fn take(&mut self) -> Option<T> {
+		self.inner.get_mut().take()
+	}
+
+	/// Consumes the cell, returning its value if it was written.
+	#[inline]
+	pub fn into_inner(self) -> Option<T> {
+		self.inner.into_inner()
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T> Default for OnceCell<T> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> Debug for OnceCell<T>
+where T: Debug
+{
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_tuple("OnceCell").field(&self.get()).finish()
+	}
+}
+
+/// The `AtomicOnceCell` has not been written.
+const EMPTY: u8 = 0;
+/// A thread is currently writing the `AtomicOnceCell`.
+const BUSY: u8 = 1;
+/// The `AtomicOnceCell` has been written.
+const READY: u8 = 2;
+
+/** A synchronized cell that can be written once.
+
+This only requires atomic compare-and-swap on bytes, and does not use the
+operating system. Threads that race to initialize the cell while another thread
+is running its initializer spin until the value is ready.
+
+## Examples
+
+```rust
+use wyz::once::AtomicOnceCell;
+
+let cell = AtomicOnceCell::new();
+assert_eq!(cell.get_or_init(|| "first"), &"first");
+assert_eq!(cell.get_or_init(|| "second"), &"first");
+```
+**/
+pub struct AtomicOnceCell<T> {
+	/// One of `EMPTY`, `BUSY`, or `READY`.
+	state: AtomicU8,
+	/// The value, which is initialized when `state` is `READY`.
+	value: UnsafeCell<MaybeUninit<T>>,
+}
+
+//  SAFETY: the value is only written by the thread that moves `state` from
+//  `EMPTY` to `BUSY`, and only read after `state` is `READY`.
+unsafe impl<T> Sync for AtomicOnceCell<T> where T: Send + Sync
+{
+}
+
+impl<T> AtomicOnceCell<T> {
+	/// Creates a new, empty, cell.
+	#[inline]
+	pub const fn new() -> Self {
+		Self {
+			state: AtomicU8::new(EMPTY),
+			value: UnsafeCell::new(MaybeUninit::uninit()),
+		}
+	}
+
+	/// Gets a reference to the value, if the cell has been written.
+	///
+	/// This does not wait for an in-progress initialization to finish.
+	#[inline]
+	pub fn get(&self) -> Option<&T> {
+		if self.state.load(Ordering::Acquire) == READY {
+			//  SAFETY: `READY` is only published after the value is written.
+			Some(unsafe { (*self.value.get()).assume_init_ref() })
+		}
+		else {
+			None
+		}
+	}
+
+	/// Gets a mutable reference to the value, if the cell has been written.
+	#[inline]
+	pub fn get_mut(&mut self) -> Option<&mut T> {
+		if *self.state.get_mut() == READY {
+			//  SAFETY: the value is written, and `&mut self` is unique.
+			Some(unsafe { self.value.get_mut().assume_init_mut() })
+		}
+		else {
+			None
+		}
+	}
+
+	/// Writes a value into the cell, if it is empty.
+	///
+	/// ## Returns
+	///
+	/// `Ok(())` if this call wrote the cell, or `Err(value)` if the cell was
+	/// already written or is being written by another thread.
+	#[inline]
+	pub fn set(&self, value: T) -> Result<(), T> {
+		if self.claim() {
+			self.publish(value);
+			Ok(())
+		}
+		else {
+			Err(value)
+		}
+	}
+
+	/// Gets the value, initializing it with `func` if the cell is empty.
+	///
+	/// If another thread is initializing the cell, this waits for it to finish
+	/// rather than running `func`.
+	///
+	/// ## Panics
+	///
+	/// This propagates any panic from `func`. The cell remains empty if `func`
+	/// panics, and another caller may try to initialize it.
+	///
+	/// Calling `get_or_init` on the same cell from within `func` deadlocks.
+	pub fn get_or_init(&self, func: impl FnOnce() -> T) -> &T {
+		let mut func = Some(func);
+		loop {
+			if let Some(value) = self.get() {
+				return value;
+			}
+			if self.claim() {
+				let reset = ScopeGuard::new(|| {
+					self.state.store(EMPTY, Ordering::Release);
+				});
+				let func = func.take().expect("the cell is only claimed once");
+				let value = func();
+				mem::forget(reset);
+				self.publish(value);
+			}
+			else {
+				hint::spin_loop();
+			}
+		}
+	}
+
+	/// Takes the value out of the cell, leaving it empty.
+	#[inline]
+	pub fn take(&mut self) -> Option<T> {
+		if *self.state.get_mut() == READY {
+			*self.state.get_mut() = EMPTY;
+			//  SAFETY: the value was written, and the state no longer says so.
+			Some(unsafe { self.value.get_mut().assume_init_read() })
+		}
+		else {
+			None
+		}
+	}
+
+	/// Consumes the cell, returning its value if it was written.
+	#[inline]
+	pub fn into_inner(mut self) -> Option<T> {
+		self.take()
+	}
+
+	/// Attempts to move the cell from `EMPTY` to `BUSY`.
+	#[inline]
+	fn claim(&self) -> bool {
+		self.state
+			.compare_exchange(EMPTY, BUSY, Ordering::Acquire, Ordering::Acquire)
+			.is_ok()
+	}
+
+	/// Writes the value into a claimed cell, and marks it as `READY`.
+	#[inline]
+	fn publish(&self, value: T) {
+		//  SAFETY: this thread holds the `BUSY` claim, so it has exclusive
+		//  access to the value.
+		unsafe {
+			(*self.value.get()).write(value);
+		}
+		self.state.store(READY, Ordering::Release);
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T> Default for AtomicOnceCell<T> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> Debug for AtomicOnceCell<T>
+where T: Debug
+{
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_tuple("AtomicOnceCell")
+			.field(&self.get())
+			.finish()
+	}
+}
+
+impl<T> Drop for AtomicOnceCell<T> {
+	#[inline]
+	fn drop(&mut self) {
+		drop(self.take());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::cell::Cell;
+
+	use super::*;
+
+	#[test]
+	fn once_cell() {
+		let mut cell = OnceCell::new();
+		assert!(cell.get().is_none());
+		assert_eq!(cell.get_or_init(|| 1), &1);
+		assert_eq!(cell.set(2), Err(2));
+		*cell.get_mut().unwrap() += 1;
+		assert_eq!(cell.take(), Some(2));
+		assert_eq!(cell.set(3), Ok(()));
+		assert_eq!(cell.into_inner(), Some(3));
+	}
+
+	#[test]
+	#[should_panic(expected = "reentrant initialization")]
+	fn reentrant() {
+		let cell = OnceCell::new();
+		cell.get_or_init(|| {
+			cell.set(1).unwrap();
+			2
+		});
+	}
+
+	#[test]
+	fn atomic_once_cell() {
+		let mut cell = AtomicOnceCell::new();
+		assert!(cell.get().is_none());
+		assert_eq!(cell.set(1), Ok(()));
+		assert_eq!(cell.get_or_init(|| 2), &1);
+		*cell.get_mut().unwrap() += 1;
+		assert_eq!(cell.take(), Some(2));
+		assert!(cell.get().is_none());
+		assert_eq!(cell.get_or_init(|| 3), &3);
+		assert_eq!(cell.into_inner(), Some(3));
+	}
+
+	#[test]
+	fn drops_value() {
+		struct Counted<'a>(&'a Cell<usize>);
+		impl Drop for Counted<'_> {
+			fn drop(&mut self) {
+				self.0.set(self.0.get() + 1);
+			}
+		}
+
+		let drops = Cell::new(0);
+		drop(AtomicOnceCell::<Counted>::new());
+		assert_eq!(drops.get(), 0);
+
+		let cell = AtomicOnceCell::new();
+		cell.get_or_init(|| Counted(&drops));
+		drop(cell);
+		assert_eq!(drops.get(), 1);
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn threads() {
+		use std::{
+			panic,
+			sync::atomic::AtomicUsize,
+			thread,
+		};
+
+		let cell = AtomicOnceCell::new();
+		let calls = AtomicUsize::new(0);
+		thread::scope(|s| {
+			for n in 0 .. 8 {
+				let (cell, calls) = (&cell, &calls);
+				s.spawn(move || {
+					cell.get_or_init(|| {
+						calls.fetch_add(1, Ordering::Relaxed);
+						n
+					});
+				});
+			}
+		});
+		assert_eq!(calls.load(Ordering::Relaxed), 1);
+		assert!(cell.get().is_some());
+
+		let cell = AtomicOnceCell::new();
+		let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+			cell.get_or_init(|| -> i32 { panic!("failed") });
+		}));
+		assert!(res.is_err());
+		assert_eq!(cell.get_or_init(|| 5), &5);
+	}
+}
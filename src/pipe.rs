@@ -0,0 +1,210 @@
+/*! Method-chaining adapters
+
+This module provides two traits that make it easier to write a computation as
+a single chain of method calls, rather than a sequence of temporary bindings.
+
+- [`Pipe`] passes a value into a function, and produces the function’s return
+  value. This lets free functions be called in suffix position.
+- [`Tap`] lends a value to a function, ignores what the function returns, and
+  produces the original value. This lets inspection or mutation be inserted
+  into a chain without breaking it.
+
+These are deliberately small. The [`tap`] crate has a much larger set of
+adapters, and is re-exported as `wyz::tap` when the `tap` feature is enabled.
+
+# Examples
+
+```rust
+use wyz::pipe::{Pipe, Tap};
+
+let total = [3, 1, 2]
+  .tap_mut(|arr| arr.sort_unstable())
+  .tap(|arr| assert_eq!(arr, &[1, 2, 3]))
+  .iter()
+  .sum::<i32>()
+  .pipe(i64::from);
+assert_eq!(total, 6);
+```
+
+[`tap`]: https://crates.io/crates/tap
+!*/
+
+/// Passes a value into a function, in suffix position.
+pub trait Pipe {
+	/// Moves `self` into `func`, and returns the function’s result.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::pipe::Pipe;
+	///
+	/// assert_eq!(5.pipe(|x| x * 2), 10);
+	/// ```
+	#[inline(always)]
+	fn pipe<R>(self, func: impl FnOnce(Self) -> R) -> R
+	where Self: Sized {
+		func(self)
+	}
+
+	/// Lends `self` to `func` immutably, and returns the function’s result.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::pipe::Pipe;
+	///
+	/// assert_eq!("hello".pipe_ref(str::len), 5);
+	/// ```
+	#[inline(always)]
+	fn pipe_ref<'a, R>(&'a self, func: impl FnOnce(&'a Self) -> R) -> R {
+		func(self)
+	}
+
+	/// Lends `self` to `func` mutably, and returns the function’s result.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::pipe::Pipe;
+	///
+	/// let mut arr = [1, 2, 3];
+	/// assert_eq!(arr.pipe_mut(|a| a.iter_mut().next()), Some(&mut 1));
+	/// ```
+	#[inline(always)]
+	fn pipe_mut<'a, R>(&'a mut self, func: impl FnOnce(&'a mut Self) -> R) -> R {
+		func(self)
+	}
+
+	/// Moves `self` into `func` if `cond` is true, or returns `self`
+	/// unchanged if it is false.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::pipe::Pipe;
+	///
+	/// assert_eq!(5.pipe_if(true, |x| x * 2), 10);
+	/// assert_eq!(5.pipe_if(false, |x| x * 2), 5);
+	/// ```
+	#[inline(always)]
+	fn pipe_if(self, cond: bool, func: impl FnOnce(Self) -> Self) -> Self
+	where Self: Sized {
+		if cond { func(self) } else { self }
+	}
+}
+
+impl<T> Pipe for T where T: ?Sized
+{
+}
+
+/// Inspects or modifies a value without leaving a method chain.
+pub trait Tap: Sized {
+	/// Lends `self` to `func` immutably, then returns `self`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::pipe::Tap;
+	///
+	/// let val = 5.tap(|x| assert_eq!(*x, 5)) + 1;
+	/// assert_eq!(val, 6);
+	/// ```
+	#[inline(always)]
+	fn tap(self, func: impl FnOnce(&Self)) -> Self {
+		func(&self);
+		self
+	}
+
+	/// Lends `self` to `func` mutably, then returns `self`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::pipe::Tap;
+	///
+	/// let arr = [3, 1, 2].tap_mut(|a| a.sort_unstable());
+	/// assert_eq!(arr, [1, 2, 3]);
+	/// ```
+	#[inline(always)]
+	fn tap_mut(mut self, func: impl FnOnce(&mut Self)) -> Self {
+		func(&mut self);
+		self
+	}
+
+	/// Lends `self` to `func` immutably if `cond` is true, then returns
+	/// `self`.
+	#[inline(always)]
+	fn tap_if(self, cond: bool, func: impl FnOnce(&Self)) -> Self {
+		if cond {
+			func(&self);
+		}
+		self
+	}
+
+	/// Lends `self` to `func` mutably if `cond` is true, then returns `self`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::pipe::Tap;
+	///
+	/// let descending = true;
+	/// let arr = [1, 2, 3].tap_mut_if(descending, |a| a.reverse());
+	/// assert_eq!(arr, [3, 2, 1]);
+	/// ```
+	#[inline(always)]
+	fn tap_mut_if(mut self, cond: bool, func: impl FnOnce(&mut Self)) -> Self {
+		if cond {
+			func(&mut self);
+		}
+		self
+	}
+
+	/// Runs `.tap()` only in builds with debug assertions enabled.
+	#[inline(always)]
+	fn tap_dbg(self, func: impl FnOnce(&Self)) -> Self {
+		self.tap_if(cfg!(debug_assertions), func)
+	}
+
+	/// Runs `.tap_mut()` only in builds with debug assertions enabled.
+	#[inline(always)]
+	fn tap_mut_dbg(self, func: impl FnOnce(&mut Self)) -> Self {
+		self.tap_mut_if(cfg!(debug_assertions), func)
+	}
+}
+
+impl<T> Tap for T {
+}
+
+#[cfg(test)]
+mod tests {
+	use core::cell::Cell;
+
+	use super::*;
+
+	#[test]
+	fn pipe() {
+		assert_eq!(2u32.pipe(|x| x + 1).pipe(u64::from), 3);
+		assert_eq!("abc".pipe_ref(str::len), 3);
+		assert_eq!(*[1, 2][..].pipe_ref(<[i32]>::first).unwrap(), 1);
+
+		let mut val = 1;
+		*val.pipe_mut(|v| v) += 1;
+		assert_eq!(val, 2);
+
+		assert_eq!(val.pipe_if(false, |_| 0), 2);
+	}
+
+	#[test]
+	fn tap() {
+		let seen = Cell::new(0);
+		let val = 1
+			.tap(|x| seen.set(*x))
+			.tap_mut(|x| *x += 1)
+			.tap_if(false, |x| seen.set(*x))
+			.tap_mut_if(true, |x| *x *= 10)
+			.tap_mut_dbg(|x| *x += 1);
+		assert_eq!(seen.get(), 1);
+		assert_eq!(val, if cfg!(debug_assertions) { 21 } else { 20 });
+	}
+}
@@ -1,9 +1,16 @@
 //! Range utilities.
 
-use core::ops::{
-	Bound,
-	Range,
-	RangeBounds,
+use core::{
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
+	ops::{
+		Bound,
+		Range,
+		RangeBounds,
+	},
 };
 
 /// Extension methods for working with various range types.
@@ -50,6 +57,115 @@ where T: Ord
 	where R: RangeExt<T>;
 }
 
+/// Extension methods for bounding range types by a collection length.
+pub trait RangeBoundsExt<T>: RangeBounds<T> {
+	/// Converts a range-like into a `Range` that lies within `0 .. len`,
+	/// such as when indexing into a collection of length `len`.
+	///
+	/// Unbounded starts become `0`, and unbounded ends become `len`.
+	///
+	/// ## Panics
+	///
+	/// This panics in all of the cases where `.try_to_range()` returns an
+	/// error.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::range::RangeBoundsExt;
+	///
+	/// assert_eq!((2 ..).to_range(5), 2 .. 5);
+	/// assert_eq!((..= 3).to_range(5), 0 .. 4);
+	/// ```
+	fn to_range(self, len: T) -> Range<T>;
+
+	/// Converts a range-like into a `Range` that lies within `0 .. len`, or
+	/// describes why it cannot.
+	///
+	/// This fails if an excluded start or included end cannot be converted to
+	/// the half-open form without overflow, if the start is after `len`, if
+	/// the start is after the end, or if the end is after `len`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use core::ops::Bound;
+	/// use wyz::range::{RangeBoundsExt, RangeError};
+	///
+	/// assert_eq!((1 .. 3).try_to_range(5), Ok(1 .. 3));
+	/// assert_eq!(
+	///   (1 .. 8).try_to_range(5),
+	///   Err(RangeError::OutOfBounds { end: 8, len: 5 }),
+	/// );
+	/// assert_eq!(
+	///   (Bound::Excluded(usize::MAX), Bound::Unbounded).try_to_range(5),
+	///   Err(RangeError::StartOverflow),
+	/// );
+	/// ```
+	fn try_to_range(self, len: T) -> Result<Range<T>, RangeError<T>>;
+}
+
+/// The reasons that a range-like cannot be converted into a bounded `Range`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RangeError<T> {
+	/// The start bound is excluded, and is the maximum value of its type, so
+	/// it has no inclusive equivalent.
+	StartOverflow,
+	/// The end bound is included, and is the maximum value of its type, so it
+	/// has no exclusive equivalent.
+	EndOverflow,
+	/// The start of the range is after its end.
+	Inverted {
+		/// The inclusive start of the range.
+		start: T,
+		/// The exclusive end of the range.
+		end: T,
+	},
+	/// The start of the range is after the length of the region it indexes.
+	StartOutOfBounds {
+		/// The inclusive start of the range.
+		start: T,
+		/// The length of the region.
+		len: T,
+	},
+	/// The end of the range is after the length of the region it indexes.
+	OutOfBounds {
+		/// The exclusive end of the range.
+		end: T,
+		/// The length of the region.
+		len: T,
+	},
+}
+
+impl<T> Display for RangeError<T>
+where T: Display
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::StartOverflow => fmt.write_str("range start index overflows"),
+			Self::EndOverflow => fmt.write_str("range end index overflows"),
+			Self::Inverted { start, end } => {
+				write!(fmt, "range starts at {} but ends at {}", start, end)
+			},
+			Self::StartOutOfBounds { start, len } => write!(
+				fmt,
+				"range start index {} out of range for length {}",
+				start, len,
+			),
+			Self::OutOfBounds { end, len } => write!(
+				fmt,
+				"range end index {} out of range for length {}",
+				end, len,
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for RangeError<T> where T: fmt::Debug + Display
+{
+}
+
 //  TODO(myrrlyn): Use funty to extend this for all integers.
 impl<R> RangeExt<usize> for R
 where R: RangeBounds<usize>
@@ -118,6 +234,47 @@ where R: RangeBounds<usize>
 	}
 }
 
+impl<R> RangeBoundsExt<usize> for R
+where R: RangeBounds<usize>
+{
+	fn to_range(self, len: usize) -> Range<usize> {
+		match self.try_to_range(len) {
+			Ok(range) => range,
+			Err(err) => panic!("{}", err),
+		}
+	}
+
+	fn try_to_range(
+		self,
+		len: usize,
+	) -> Result<Range<usize>, RangeError<usize>> {
+		let start = match self.start_bound() {
+			Bound::Unbounded => 0,
+			Bound::Included(&v) => v,
+			Bound::Excluded(&v) => {
+				v.checked_add(1).ok_or(RangeError::StartOverflow)?
+			},
+		};
+		let end = match self.end_bound() {
+			Bound::Unbounded => len,
+			Bound::Included(&v) => {
+				v.checked_add(1).ok_or(RangeError::EndOverflow)?
+			},
+			Bound::Excluded(&v) => v,
+		};
+		if start > len {
+			return Err(RangeError::StartOutOfBounds { start, len });
+		}
+		if start > end {
+			return Err(RangeError::Inverted { start, end });
+		}
+		if end > len {
+			return Err(RangeError::OutOfBounds { end, len });
+		}
+		Ok(start .. end)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -155,6 +312,44 @@ mod tests {
 		assert!(!r.contains(&11));
 	}
 
+	#[test]
+	fn to_range() {
+		assert_eq!((..).to_range(4), 0 .. 4);
+		assert_eq!((1 ..= 2).to_range(4), 1 .. 3);
+		assert_eq!((Bound::Excluded(0), Bound::Included(3)).to_range(4), 1 .. 4);
+		assert_eq!((4 ..).to_range(4), 4 .. 4);
+
+		let max = usize::MAX;
+		assert_eq!((.. max).try_to_range(max), Ok(0 .. max));
+		assert_eq!((..= max).try_to_range(max), Err(RangeError::EndOverflow));
+		assert_eq!(
+			(Bound::Excluded(max), Bound::Excluded(max)).try_to_range(max),
+			Err(RangeError::StartOverflow),
+		);
+		assert_eq!(
+			(Bound::Included(3), Bound::Excluded(2)).try_to_range(4),
+			Err(RangeError::Inverted { start: 3, end: 2 }),
+		);
+		assert_eq!(
+			(5 ..).try_to_range(4),
+			Err(RangeError::StartOutOfBounds { start: 5, len: 4 }),
+		);
+		assert_eq!(
+			(6 .. 8).try_to_range(4),
+			Err(RangeError::StartOutOfBounds { start: 6, len: 4 }),
+		);
+		assert_eq!(
+			(0 ..= 4).try_to_range(4),
+			Err(RangeError::OutOfBounds { end: 5, len: 4 }),
+		);
+	}
+
+	#[test]
+	#[should_panic(expected = "range end index 5 out of range for length 4")]
+	fn to_range_panics() {
+		(2 .. 5).to_range(4);
+	}
+
 	#[test]
 	fn intersect() {
 		let a = 3 .. 10;
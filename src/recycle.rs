@@ -0,0 +1,401 @@
+/*! Object recycling
+
+Values that own heap buffers, such as `Vec` and `String`, are expensive to
+create and cheap to empty. When a program repeatedly builds and discards them,
+it can instead keep the discarded values and reuse their allocations.
+
+A [`Recycler`] is a pool of such values. [`Recycler::get`] hands out a value from
+the pool, or creates a new one if the pool is empty, wrapped in a [`Pooled`]
+guard. When the guard is dropped, the value is reset and returned to the pool
+rather than being destroyed. Values are reset either by the [`Recycle`] trait,
+or by a function given to the recycler.
+
+The pool uses a `RefCell`, so a `Recycler` belongs to a single thread.
+
+# Examples
+
+```rust
+use wyz::recycle::Recycler;
+
+let lines = Recycler::<String>::new();
+for n in 0 .. 3 {
+ let mut line = lines.get();
+ assert!(line.is_empty());
+ line.push_str("line ");
+ line.push_str(&n.to_string());
+}
+// Only one `String` was ever allocated.
+assert_eq!(lines.len(), 1);
+```
+!*/
+
+#![cfg(feature = "alloc")]
+
+use alloc::{
+	collections::{
+		BTreeMap,
+		BTreeSet,
+		BinaryHeap,
+		VecDeque,
+	},
+	string::String,
+	vec::Vec,
+};
+use core::{
+	cell::RefCell,
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	mem::ManuallyDrop,
+	ops::{
+		Deref,
+		DerefMut,
+	},
+};
+
+/// Resets a value to a blank state, while keeping any resources it owns.
+pub trait Recycle {
+	/// Resets the value, such as by clearing a collection while keeping its
+	/// allocation.
+	fn recycle(&mut self);
+}
+
+macro_rules! recycle_clear {
+	($($t:ident $(<$($g:ident),+>)?),+ $(,)?) => { $(
+		impl$(<$($g),+>)? Recycle for $t$(<$($g),+>)? {
+			#[inline]
+			fn recycle(&mut self) {
+				self.clear();
+			}
+		}
+	)+ };
+}
+
+recycle_clear!(
+	String,
+	Vec<T>,
+	VecDeque<T>,
+	BinaryHeap<T>,
+	BTreeMap<K, V>,
+	BTreeSet<T>,
+);
+
+#[cfg(feature = "std")]
+impl<K, V, S> Recycle for std::collections::HashMap<K, V, S> {
+	#[inline]
+	fn recycle(&mut self) {
+		self.clear();
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T, S> Recycle for std::collections::HashSet<T, S> {
+	#[inline]
+	fn recycle(&mut self) {
+		self.clear();
+	}
+}
+
+/** A pool of reusable values.
+
+## Type Parameters
+
+- `T`: The type of the pooled values.
+- `C`: The function that creates new values when the pool is empty.
+- `R`: The function that resets values when they return to the pool.
+**/
+pub struct Recycler<T, C = fn() -> T, R = fn(&mut T)>
+where
+	C: Fn() -> T,
+	R: Fn(&mut T),
+{
+	/// Values waiting to be reused.
+	pool: RefCell<Vec<T>>,
+	/// Creates new values.
+	create: C,
+	/// Resets values that return to the pool.
+	reset: R,
+	/// The most values that the pool will hold.
+	limit: usize,
+}
+
+impl<T> Recycler<T>
+where T: Default + Recycle
+{
+	/// Creates an empty pool of values that are created with `Default` and
+	/// reset with `Recycle`.
+	#[inline]
+	pub fn new() -> Self {
+		Self::with(T::default, T::recycle)
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T> Default for Recycler<T>
+where T: Default + Recycle
+{
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T, C, R> Recycler<T, C, R>
+where
+	C: Fn() -> T,
+	R: Fn(&mut T),
+{
+	/// Creates an empty pool that makes new values with `create`, and resets
+	/// them with `reset`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::recycle::Recycler;
+	///
+	/// let buffers = Recycler::with(|| vec![0u8; 4], |buf| buf.fill(0));
+	/// {
+	///  let mut buf = buffers.get();
+	///  buf[0] = 5;
+	/// }
+	/// assert_eq!(*buffers.get(), [0; 4]);
+	/// ```
+	#[inline]
+	pub fn with(create: C, reset: R) -> Self {
+		Self {
+			pool: RefCell::new(Vec::new()),
+			create,
+			reset,
+			limit: usize::MAX,
+		}
+	}
+
+	/// Sets the most values that the pool will hold. Values that return to a
+	/// full pool are dropped.
+	#[inline]
+	pub fn limit(mut self, limit: usize) -> Self {
+		self.limit = limit;
+		self.pool.get_mut().truncate(limit);
+		self
+	}
+
+	/// Takes a value from the pool, or creates one if the pool is empty.
+	///
+	/// The value returns to the pool when the guard is dropped.
+	#[inline]
+	pub fn get(&self) -> Pooled<'_, T, C, R> {
+		let value = self.pool.borrow_mut().pop();
+		Pooled {
+			value: ManuallyDrop::new(value.unwrap_or_else(&self.create)),
+			recycler: self,
+		}
+	}
+
+	/// Resets a value and adds it to the pool.
+	///
+	/// If the pool is already full, the value is dropped without being reset.
+	#[inline]
+	pub fn put(&self, mut value: T) {
+		if self.pool.borrow().len() >= self.limit {
+			return;
+		}
+		(self.reset)(&mut value);
+		//  The reset function may itself have filled the pool.
+		let mut pool = self.pool.borrow_mut();
+		if pool.len() < self.limit {
+			pool.push(value);
+		}
+	}
+
+	/// Counts the values waiting in the pool.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.pool.borrow().len()
+	}
+
+	/// Tests whether the pool is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Drops all the values waiting in the pool.
+	#[inline]
+	pub fn clear(&self) {
+		self.pool.borrow_mut().clear();
+	}
+}
+
+impl<T, C, R> Debug for Recycler<T, C, R>
+where
+	C: Fn() -> T,
+	R: Fn(&mut T),
+{
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_struct("Recycler")
+			.field("len", &self.len())
+			.field("limit", &self.limit)
+			.finish()
+	}
+}
+
+/// A value taken from a [`Recycler`], which returns to it when dropped.
+pub struct Pooled<'a, T, C = fn() -> T, R = fn(&mut T)>
+where
+	C: Fn() -> T,
+	R: Fn(&mut T),
+{
+	/// The value. It is only taken out in `Drop` or `into_inner`.
+	value: ManuallyDrop<T>,
+	/// The pool to which the value returns.
+	recycler: &'a Recycler<T, C, R>,
+}
+
+impl<T, C, R> Pooled<'_, T, C, R>
+where
+	C: Fn() -> T,
+	R: Fn(&mut T),
+{
+	/// Removes the value from the pool’s management, so that it is not
+	/// recycled.
+	#[inline]
+	pub fn into_inner(self) -> T {
+		let mut this = ManuallyDrop::new(self);
+		//  SAFETY: `this` is never dropped, so `value` is not taken twice.
+		unsafe { ManuallyDrop::take(&mut this.value) }
+	}
+}
+
+impl<T, C, R> Deref for Pooled<'_, T, C, R>
+where
+	C: Fn() -> T,
+	R: Fn(&mut T),
+{
+	type Target = T;
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target {
+		&self.value
+	}
+}
+
+impl<T, C, R> DerefMut for Pooled<'_, T, C, R>
+where
+	C: Fn() -> T,
+	R: Fn(&mut T),
+{
+	#[inline(always)]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.value
+	}
+}
+
+impl<T, C, R> Debug for Pooled<'_, T, C, R>
+where
+	T: Debug,
+	C: Fn() -> T,
+	R: Fn(&mut T),
+{
+	#[inline(always)]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Debug::fmt(&*self.value, fmt)
+	}
+}
+
+impl<T, C, R> Drop for Pooled<'_, T, C, R>
+where
+	C: Fn() -> T,
+	R: Fn(&mut T),
+{
+	#[inline]
+	fn drop(&mut self) {
+		//  SAFETY: this is the only place, other than `into_inner` (which
+		//  prevents this destructor from running), that takes `value`.
+		let value = unsafe { ManuallyDrop::take(&mut self.value) };
+		self.recycler.put(value);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::cell::Cell;
+
+	use super::*;
+
+	#[test]
+	fn reuse() {
+		let pool = Recycler::<Vec<u8>>::new();
+		assert!(pool.is_empty());
+		{
+			let mut a = pool.get();
+			let mut b = pool.get();
+			a.extend_from_slice(&[1; 64]);
+			b.push(2);
+		}
+		assert_eq!(pool.len(), 2);
+
+		let a = pool.get();
+		let b = pool.get();
+		assert!(a.is_empty() && b.is_empty());
+		assert!(a.capacity() >= 1 && b.capacity() >= 1);
+		assert!(pool.is_empty());
+
+		let kept = a.into_inner();
+		drop(b);
+		assert_eq!(pool.len(), 1);
+		assert!(kept.capacity() >= 1);
+
+		pool.clear();
+		assert!(pool.is_empty());
+	}
+
+	#[test]
+	fn custom() {
+		let made = Cell::new(0);
+		let pool = Recycler::with(
+			|| {
+				made.set(made.get() + 1);
+				0u32
+			},
+			|n| *n = 0,
+		)
+		.limit(1);
+
+		{
+			let mut a = pool.get();
+			let b = pool.get();
+			*a = 5;
+			drop(b);
+		}
+		assert_eq!(made.get(), 2);
+		assert_eq!(pool.len(), 1);
+		assert_eq!(*pool.get(), 0);
+		assert_eq!(made.get(), 2);
+	}
+
+	#[test]
+	fn full() {
+		let resets = Cell::new(0);
+		let pool = Recycler::with(Vec::<u8>::new, |v| {
+			resets.set(resets.get() + 1);
+			v.clear();
+		})
+		.limit(1);
+
+		pool.put(Vec::from([1]));
+		pool.put(Vec::from([2]));
+		assert_eq!(pool.len(), 1);
+		assert_eq!(resets.get(), 1);
+	}
+
+	#[test]
+	fn recycle() {
+		let mut text = String::from("text");
+		text.recycle();
+		assert!(text.is_empty());
+		assert!(text.capacity() >= 4);
+	}
+}
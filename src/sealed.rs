@@ -0,0 +1,130 @@
+/*! `sealed!` macro
+
+A sealed trait is a public trait that cannot be implemented outside the crate
+that defines it. This lets the crate add methods to the trait, or rely on its
+set of implementors being fixed, without breaking downstream code.
+
+The pattern is to give the trait a supertrait that is public, but declared in a
+private module, so that other crates can use it but cannot name it. The
+`sealed!` macro writes that module, its `Sealed` trait, and the `Sealed`
+implementations, from one declaration.
+
+The macro takes a module declaration, and then a comma-separated list of the
+types that may implement the sealed trait. Generic types are introduced with
+`impl<…>`, as in an `impl` block header; bounds are unnecessary, since they can
+be placed on the implementations of the public trait instead. The generic
+parameter list cannot contain `>`, other than the one that closes it.
+
+# Examples
+
+```rust
+use wyz::sealed;
+
+sealed! {
+  mod seal;
+  u8, u16, u32,
+  impl<T> [T],
+  impl<T: ?Sized> &T,
+}
+
+/// Unsigned integers up to 32 bits wide.
+pub trait Small: seal::Sealed + Copy {}
+
+impl Small for u8 {}
+impl Small for u16 {}
+impl Small for u32 {}
+```
+!*/
+
+/// `sealed!` macro
+#[macro_export]
+macro_rules! sealed {
+	( $( #[$attr:meta] )* $vis:vis mod $module:ident ; $( $rest:tt )* ) => {
+		$( #[$attr] )*
+		#[doc(hidden)]
+		$vis mod $module {
+			/// Prevents other crates from implementing traits that require
+			/// it.
+			#[doc(hidden)]
+			pub trait Sealed {}
+		}
+
+		$crate::sealed!(@impls [ $module ] $( $rest )*);
+	};
+
+	( @impls [ $module:ident ] $(,)? ) => {};
+
+	( @impls [ $module:ident ] impl < $( $rest:tt )* ) => {
+		$crate::sealed!(@generics [ $module ] [] $( $rest )*);
+	};
+
+	( @impls [ $module:ident ] $typ:ty $( , $( $rest:tt )* )? ) => {
+		impl $module::Sealed for $typ {}
+
+		$crate::sealed!(@impls [ $module ] $( $( $rest )* )?);
+	};
+
+	(
+		@generics [ $module:ident ] [ $( $gen:tt )* ]
+		> $typ:ty $( , $( $rest:tt )* )?
+	) => {
+		impl< $( $gen )* > $module::Sealed for $typ {}
+
+		$crate::sealed!(@impls [ $module ] $( $( $rest )* )?);
+	};
+
+	(
+		@generics [ $module:ident ] [ $( $gen:tt )* ]
+		$next:tt $( $rest:tt )*
+	) => {
+		$crate::sealed!(@generics [ $module ] [ $( $gen )* $next ] $( $rest )*);
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use core::marker::PhantomData;
+
+	struct Local;
+
+	struct Wrapper<T>(PhantomData<T>);
+
+	sealed! {
+		mod seal;
+		u8,
+		Local,
+		impl<T> Wrapper<T>,
+		impl<T, const N: usize> [T; N],
+		impl<T: ?Sized> &T,
+	}
+
+	trait Marked: seal::Sealed {
+		fn marked(&self) -> bool {
+			true
+		}
+	}
+
+	impl Marked for u8 {
+	}
+
+	impl Marked for Local {
+	}
+
+	impl<T> Marked for Wrapper<T> {
+	}
+
+	impl<T, const N: usize> Marked for [T; N] {
+	}
+
+	impl<T: ?Sized> Marked for &T {
+	}
+
+	#[test]
+	fn implementors() {
+		assert!(5u8.marked());
+		assert!(Local.marked());
+		assert!(Wrapper::<i32>(PhantomData).marked());
+		assert!([(); 3].marked());
+		assert!((&"str").marked());
+	}
+}
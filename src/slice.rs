@@ -0,0 +1,281 @@
+//! Slice utilities.
+
+use core::slice;
+
+/// Extension methods for slices that the standard library does not (yet)
+/// provide.
+pub trait SliceExt<T> {
+	/// Gets mutable references to two different elements at once.
+	///
+	/// ## Returns
+	///
+	/// `None` if `a` and `b` are equal, or if either is out of bounds.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::slice::SliceExt;
+	///
+	/// let mut arr = [1, 2, 3];
+	/// if let Some((a, b)) = arr.get_two_mut(0, 2) {
+	///   core::mem::swap(a, b);
+	/// }
+	/// assert_eq!(arr, [3, 2, 1]);
+	/// assert!(arr.get_two_mut(1, 1).is_none());
+	/// ```
+	fn get_two_mut(&mut self, a: usize, b: usize) -> Option<(&mut T, &mut T)>;
+
+	/// Splits a slice into the elements before `idx`, the element at `idx`,
+	/// and the elements after it.
+	///
+	/// ## Returns
+	///
+	/// `None` if `idx` is out of bounds.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::slice::SliceExt;
+	///
+	/// let (before, at, after) = [1, 2, 3, 4].split_around(1).unwrap();
+	/// assert_eq!(before, &[1]);
+	/// assert_eq!(*at, 2);
+	/// assert_eq!(after, &[3, 4]);
+	/// ```
+	fn split_around(&self, idx: usize) -> Option<(&[T], &T, &[T])>;
+
+	/// Splits a slice mutably into the elements before `idx`, the element at
+	/// `idx`, and the elements after it.
+	///
+	/// ## Returns
+	///
+	/// `None` if `idx` is out of bounds.
+	fn split_around_mut(
+		&mut self,
+		idx: usize,
+	) -> Option<(&mut [T], &mut T, &mut [T])>;
+
+	/// Views a slice as a sequence of `N`-element arrays, followed by the
+	/// remainder that is too short to fill an array.
+	///
+	/// ## Panics
+	///
+	/// This panics if `N` is zero.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::slice::SliceExt;
+	///
+	/// let (pairs, rest) = [1, 2, 3, 4, 5].as_array_chunks::<2>();
+	/// assert_eq!(pairs, &[[1, 2], [3, 4]]);
+	/// assert_eq!(rest, &[5]);
+	/// ```
+	fn as_array_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]);
+
+	/// Views a slice mutably as a sequence of `N`-element arrays, followed by
+	/// the remainder that is too short to fill an array.
+	///
+	/// ## Panics
+	///
+	/// This panics if `N` is zero.
+	fn as_array_chunks_mut<const N: usize>(
+		&mut self,
+	) -> (&mut [[T; N]], &mut [T]);
+
+	/// Finds the index of the first occurrence of `needle` in the slice.
+	///
+	/// An empty `needle` is found at index `0`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::slice::SliceExt;
+	///
+	/// assert_eq!(b"hello world".find_subslice(b"o w"), Some(4));
+	/// assert_eq!(b"hello world".find_subslice(b"xyz"), None);
+	/// ```
+	fn find_subslice(&self, needle: &[T]) -> Option<usize>
+	where T: PartialEq;
+
+	/// Removes leading elements that satisfy `pred`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::slice::SliceExt;
+	///
+	/// let data = [0, 0, 1, 2, 0];
+	/// assert_eq!(data.trim_start_matches(|&x| x == 0), &[1, 2, 0]);
+	/// ```
+	fn trim_start_matches(&self, pred: impl FnMut(&T) -> bool) -> &[T];
+
+	/// Removes trailing elements that satisfy `pred`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::slice::SliceExt;
+	///
+	/// let data = [0, 1, 2, 0, 0];
+	/// assert_eq!(data.trim_end_matches(|&x| x == 0), &[0, 1, 2]);
+	/// ```
+	fn trim_end_matches(&self, pred: impl FnMut(&T) -> bool) -> &[T];
+
+	/// Removes both leading and trailing elements that satisfy `pred`.
+	fn trim_matches(&self, mut pred: impl FnMut(&T) -> bool) -> &[T] {
+		self.trim_start_matches(&mut pred).trim_end_matches(pred)
+	}
+}
+
+impl<T> SliceExt<T> for [T] {
+	fn get_two_mut(&mut self, a: usize, b: usize) -> Option<(&mut T, &mut T)> {
+		if a == b || a >= self.len() || b >= self.len() {
+			return None;
+		}
+		let (lo, hi) = (a.min(b), a.max(b));
+		let (head, tail) = self.split_at_mut(hi);
+		let (lo, hi) = (&mut head[lo], &mut tail[0]);
+		Some(if a < b { (lo, hi) } else { (hi, lo) })
+	}
+
+	fn split_around(&self, idx: usize) -> Option<(&[T], &T, &[T])> {
+		if idx >= self.len() {
+			return None;
+		}
+		let (before, rest) = self.split_at(idx);
+		let (at, after) = rest.split_first()?;
+		Some((before, at, after))
+	}
+
+	fn split_around_mut(
+		&mut self,
+		idx: usize,
+	) -> Option<(&mut [T], &mut T, &mut [T])> {
+		if idx >= self.len() {
+			return None;
+		}
+		let (before, rest) = self.split_at_mut(idx);
+		let (at, after) = rest.split_first_mut()?;
+		Some((before, at, after))
+	}
+
+	fn as_array_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]) {
+		assert!(N != 0, "chunk size must be non-zero");
+		let (chunks, rest) = self.split_at(self.len() / N * N);
+		//  SAFETY: `[T; N]` has the same layout as `N` consecutive `T`s, and
+		//  `chunks` holds exactly `len / N` groups of `N`.
+		let chunks = unsafe {
+			slice::from_raw_parts(
+				chunks.as_ptr() as *const [T; N],
+				chunks.len() / N,
+			)
+		};
+		(chunks, rest)
+	}
+
+	fn as_array_chunks_mut<const N: usize>(
+		&mut self,
+	) -> (&mut [[T; N]], &mut [T]) {
+		assert!(N != 0, "chunk size must be non-zero");
+		let len = self.len() / N * N;
+		let (chunks, rest) = self.split_at_mut(len);
+		//  SAFETY: as in `as_array_chunks`, and `chunks` is uniquely borrowed.
+		let chunks = unsafe {
+			slice::from_raw_parts_mut(
+				chunks.as_mut_ptr() as *mut [T; N],
+				chunks.len() / N,
+			)
+		};
+		(chunks, rest)
+	}
+
+	fn find_subslice(&self, needle: &[T]) -> Option<usize>
+	where T: PartialEq {
+		if needle.is_empty() {
+			return Some(0);
+		}
+		self.windows(needle.len())
+			.position(|window| window == needle)
+	}
+
+	fn trim_start_matches(&self, mut pred: impl FnMut(&T) -> bool) -> &[T] {
+		let start = self.iter().position(|x| !pred(x)).unwrap_or(self.len());
+		&self[start ..]
+	}
+
+	fn trim_end_matches(&self, mut pred: impl FnMut(&T) -> bool) -> &[T] {
+		let end = self.iter().rposition(|x| !pred(x)).map_or(0, |i| i + 1);
+		&self[.. end]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn get_two_mut() {
+		let mut arr = [1, 2, 3];
+		let (a, b) = arr.get_two_mut(2, 0).unwrap();
+		assert_eq!((*a, *b), (3, 1));
+		*a = 30;
+		*b = 10;
+		assert_eq!(arr, [10, 2, 30]);
+		assert!(arr.get_two_mut(0, 3).is_none());
+		assert!(arr.get_two_mut(0, 0).is_none());
+	}
+
+	#[test]
+	fn split_around() {
+		let mut arr = [1, 2, 3];
+		assert_eq!(arr.split_around(0), Some((&[][..], &1, &[2, 3][..])));
+		assert_eq!(arr.split_around(2), Some((&[1, 2][..], &3, &[][..])));
+		assert!(arr.split_around(3).is_none());
+
+		let (before, at, _) = arr.split_around_mut(1).unwrap();
+		before[0] = *at;
+		assert_eq!(arr, [2, 2, 3]);
+	}
+
+	#[test]
+	fn array_chunks() {
+		let mut arr = [1, 2, 3, 4, 5, 6, 7];
+		let (chunks, rest) = arr.as_array_chunks::<3>();
+		assert_eq!(chunks, &[[1, 2, 3], [4, 5, 6]]);
+		assert_eq!(rest, &[7]);
+
+		let (chunks, rest) = arr.as_array_chunks_mut::<4>();
+		chunks[0].reverse();
+		rest[0] = 0;
+		assert_eq!(arr, [4, 3, 2, 1, 0, 6, 7]);
+
+		let (chunks, rest) = arr[.. 2].as_array_chunks::<3>();
+		assert!(chunks.is_empty());
+		assert_eq!(rest, &[4, 3]);
+	}
+
+	#[test]
+	#[should_panic(expected = "chunk size must be non-zero")]
+	fn zero_chunks() {
+		let _ = [1, 2].as_array_chunks::<0>();
+	}
+
+	#[test]
+	fn find_subslice() {
+		let data = [1, 2, 3, 2, 3];
+		assert_eq!(data.find_subslice(&[2, 3]), Some(1));
+		assert_eq!(data.find_subslice(&[]), Some(0));
+		assert_eq!(data.find_subslice(&[3, 1]), None);
+		assert_eq!(data[.. 1].find_subslice(&[1, 2]), None);
+	}
+
+	#[test]
+	fn trim_matches() {
+		let data = [0, 1, 0, 2, 0];
+		assert_eq!(data.trim_start_matches(|&x| x == 0), &[1, 0, 2, 0]);
+		assert_eq!(data.trim_end_matches(|&x| x == 0), &[0, 1, 0, 2]);
+		assert_eq!(data.trim_matches(|&x| x == 0), &[1, 0, 2]);
+		assert!(data.trim_matches(|&x| x < 5).is_empty());
+	}
+}
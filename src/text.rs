@@ -0,0 +1,215 @@
+//! Text utilities.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// Extension methods for string slices, for the text massaging that
+/// command-line programs frequently need.
+///
+/// The case-insensitive methods only fold ASCII letters, like
+/// [`str::eq_ignore_ascii_case`].
+pub trait StrExt: seal::Sealed {
+	/// Shortens a string to at most `len` bytes, without splitting a
+	/// character.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::text::StrExt;
+	///
+	/// assert_eq!("héllo".truncate_to_boundary(2), "h");
+	/// assert_eq!("héllo".truncate_to_boundary(3), "hé");
+	/// assert_eq!("héllo".truncate_to_boundary(10), "héllo");
+	/// ```
+	fn truncate_to_boundary(&self, len: usize) -> &str;
+
+	/// Tests whether a string starts with `prefix`, ignoring ASCII case.
+	fn starts_with_ci(&self, prefix: &str) -> bool {
+		self.strip_prefix_ci(prefix).is_some()
+	}
+
+	/// Tests whether a string ends with `suffix`, ignoring ASCII case.
+	fn ends_with_ci(&self, suffix: &str) -> bool {
+		self.strip_suffix_ci(suffix).is_some()
+	}
+
+	/// Removes `prefix` from the start of a string, ignoring ASCII case.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::text::StrExt;
+	///
+	/// assert_eq!("Host: a.b".strip_prefix_ci("host:"), Some(" a.b"));
+	/// assert_eq!("Accept: */*".strip_prefix_ci("host:"), None);
+	/// ```
+	fn strip_prefix_ci(&self, prefix: &str) -> Option<&str>;
+
+	/// Removes `suffix` from the end of a string, ignoring ASCII case.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::text::StrExt;
+	///
+	/// assert_eq!("README.MD".strip_suffix_ci(".md"), Some("README"));
+	/// ```
+	fn strip_suffix_ci(&self, suffix: &str) -> Option<&str>;
+
+	/// Puts `prefix` at the start of every line that is not empty.
+	///
+	/// Line endings are preserved, and empty lines are left alone so that they
+	/// do not gain trailing whitespace.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::text::StrExt;
+	///
+	/// assert_eq!("a\n\nb\n".indent("  "), "  a\n\n  b\n");
+	/// ```
+	#[cfg(feature = "alloc")]
+	fn indent(&self, prefix: &str) -> String;
+
+	/// Wraps text so that no line is longer than `width` characters.
+	///
+	/// Words are separated by whitespace, and runs of whitespace within a line
+	/// collapse to one space. Existing line breaks are kept. A word that is
+	/// longer than `width` is placed on a line of its own, and not broken.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use wyz::text::StrExt;
+	///
+	/// assert_eq!(
+	///   "the quick brown fox jumps".wrap(10),
+	///   "the quick\nbrown fox\njumps",
+	/// );
+	/// ```
+	#[cfg(feature = "alloc")]
+	fn wrap(&self, width: usize) -> String;
+}
+
+impl StrExt for str {
+	fn truncate_to_boundary(&self, mut len: usize) -> &str {
+		if len >= self.len() {
+			return self;
+		}
+		while !self.is_char_boundary(len) {
+			len -= 1;
+		}
+		&self[.. len]
+	}
+
+	fn strip_prefix_ci(&self, prefix: &str) -> Option<&str> {
+		let len = prefix.len();
+		let head = self.as_bytes().get(.. len)?;
+		if head.eq_ignore_ascii_case(prefix.as_bytes()) {
+			//  ASCII folding preserves UTF-8 structure, so `len` is a
+			//  character boundary in `self` just as it is in `prefix`.
+			Some(&self[len ..])
+		}
+		else {
+			None
+		}
+	}
+
+	fn strip_suffix_ci(&self, suffix: &str) -> Option<&str> {
+		let split = self.len().checked_sub(suffix.len())?;
+		if self.as_bytes()[split ..].eq_ignore_ascii_case(suffix.as_bytes()) {
+			Some(&self[.. split])
+		}
+		else {
+			None
+		}
+	}
+
+	#[cfg(feature = "alloc")]
+	fn indent(&self, prefix: &str) -> String {
+		let mut out = String::with_capacity(self.len());
+		for line in self.split_inclusive('\n') {
+			if !line.trim_end_matches(&['\r', '\n'][..]).is_empty() {
+				out.push_str(prefix);
+			}
+			out.push_str(line);
+		}
+		out
+	}
+
+	#[cfg(feature = "alloc")]
+	fn wrap(&self, width: usize) -> String {
+		let mut out = String::with_capacity(self.len());
+		for (idx, line) in self.lines().enumerate() {
+			if idx != 0 {
+				out.push('\n');
+			}
+			let mut col = 0;
+			for word in line.split_whitespace() {
+				let len = word.chars().count();
+				if col != 0 && col + 1 + len > width {
+					out.push('\n');
+					col = 0;
+				}
+				else if col != 0 {
+					out.push(' ');
+					col += 1;
+				}
+				out.push_str(word);
+				col += len;
+			}
+		}
+		out
+	}
+}
+
+crate::sealed! {
+	mod seal;
+	str,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn truncate() {
+		assert_eq!("".truncate_to_boundary(0), "");
+		assert_eq!("abc".truncate_to_boundary(0), "");
+		assert_eq!("a🦀".truncate_to_boundary(4), "a");
+		assert_eq!("a🦀".truncate_to_boundary(5), "a🦀");
+	}
+
+	#[test]
+	fn case_insensitive() {
+		assert_eq!("HeLLo".strip_prefix_ci("hell"), Some("o"));
+		assert_eq!("hé".strip_prefix_ci("HÉ"), None);
+		assert_eq!("hé".strip_prefix_ci("h"), Some("é"));
+		assert_eq!("é".strip_prefix_ci("\u{c3}"), None);
+		assert_eq!("ab".strip_prefix_ci("abc"), None);
+		assert_eq!("File.TXT".strip_suffix_ci(".txt"), Some("File"));
+		assert_eq!("txt".strip_suffix_ci(".txt"), None);
+		assert!("Hello".starts_with_ci("HE"));
+		assert!("Hello".ends_with_ci("LO"));
+		assert!(!"Hello".ends_with_ci("he"));
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn indent() {
+		assert_eq!("".indent("> "), "");
+		assert_eq!("a".indent("> "), "> a");
+		assert_eq!("a\r\n\r\nb".indent("> "), "> a\r\n\r\n> b");
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn wrap() {
+		assert_eq!("".wrap(5), "");
+		assert_eq!("a  b   c".wrap(80), "a b c");
+		assert_eq!("aa bb\ncc dd".wrap(5), "aa bb\ncc dd");
+		assert_eq!("aa bb cc".wrap(4), "aa\nbb\ncc");
+		assert_eq!("a verylongword b".wrap(4), "a\nverylongword\nb");
+		assert_eq!("é é é".wrap(3), "é é\né");
+	}
+}
@@ -0,0 +1,243 @@
+/*! Typestate building blocks
+
+A typestate builder records which of its fields have been filled in its type
+parameters, so that calling `.build()` before every required field is set is a
+compile error rather than a runtime one. This module provides the pieces that
+such builders share:
+
+- [`Unset`] and [`Set<T>`] are the two states of a builder field.
+- [`Field<T>`] is implemented by both states, for fields that are optional.
+- [`Complete`] is implemented by tuples in which every element is `Set`, and
+  unwraps them all at once.
+
+# Examples
+
+```rust
+use wyz::typestate::{Complete, Field, Set, Unset};
+
+struct Server {
+ host: String,
+ port: u16,
+ threads: usize,
+}
+
+struct Builder<H, P, T> {
+ host: H,
+ port: P,
+ threads: T,
+}
+
+impl Builder<Unset, Unset, Unset> {
+ fn new() -> Self {
+  Self { host: Unset, port: Unset, threads: Unset }
+ }
+}
+
+impl<P, T> Builder<Unset, P, T> {
+ fn host(self, host: &str) -> Builder<Set<String>, P, T> {
+  Builder { host: Set(host.into()), port: self.port, threads: self.threads }
+ }
+}
+
+impl<H, T> Builder<H, Unset, T> {
+ fn port(self, port: u16) -> Builder<H, Set<u16>, T> {
+  Builder { host: self.host, port: Set(port), threads: self.threads }
+ }
+}
+
+impl<H, P> Builder<H, P, Unset> {
+ fn threads(self, threads: usize) -> Builder<H, P, Set<usize>> {
+  Builder { host: self.host, port: self.port, threads: Set(threads) }
+ }
+}
+
+impl<H, P, T> Builder<H, P, T>
+where
+ (H, P): Complete<Output = (String, u16)>,
+ T: Field<usize>,
+{
+ fn build(self) -> Server {
+  let (host, port) = (self.host, self.port).complete();
+  let threads = self.threads.unwrap_or(1);
+  Server { host, port, threads }
+ }
+}
+
+let server = Builder::new().port(80).host("localhost").build();
+assert_eq!(server.threads, 1);
+```
+
+Leaving out a required field does not compile:
+
+```rust,compile_fail
+# use wyz::typestate::{Complete, Set, Unset};
+# struct Builder<H, P> { host: H, port: P }
+# impl<H, P> Builder<H, P> where (H, P): Complete<Output = (String, u16)> {
+#  fn build(self) {}
+# }
+Builder { host: Set(String::new()), port: Unset }.build();
+```
+!*/
+
+/// A builder field that has not been given a value.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Unset;
+
+/// A builder field that holds a value.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Set<T>(pub T);
+
+impl<T> Set<T> {
+	/// Unwraps the value.
+	#[inline(always)]
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+/// A builder field that may or may not hold a value of type `T`.
+///
+/// Builders use this as a bound on optional fields, so that they can be built
+/// in either state.
+pub trait Field<T> {
+	/// Converts the field into its value, if it has one.
+	fn into_option(self) -> Option<T>;
+
+	/// Views the field’s value, if it has one.
+	fn as_option(&self) -> Option<&T>;
+
+	/// Tests whether the field holds a value.
+	#[inline]
+	fn is_set(&self) -> bool {
+		self.as_option().is_some()
+	}
+
+	/// Converts the field into its value, or `default` if it has none.
+	#[inline]
+	fn unwrap_or(self, default: T) -> T
+	where Self: Sized {
+		self.into_option().unwrap_or(default)
+	}
+
+	/// Converts the field into its value, or calls `func` if it has none.
+	#[inline]
+	fn unwrap_or_else(self, func: impl FnOnce() -> T) -> T
+	where Self: Sized {
+		self.into_option().unwrap_or_else(func)
+	}
+}
+
+impl<T> Field<T> for Unset {
+	#[inline(always)]
+	fn into_option(self) -> Option<T> {
+		None
+	}
+
+	#[inline(always)]
+	fn as_option(&self) -> Option<&T> {
+		None
+	}
+}
+
+impl<T> Field<T> for Set<T> {
+	#[inline(always)]
+	fn into_option(self) -> Option<T> {
+		Some(self.0)
+	}
+
+	#[inline(always)]
+	fn as_option(&self) -> Option<&T> {
+		Some(&self.0)
+	}
+}
+
+/// A collection of builder fields that have all been given values.
+///
+/// This is implemented for `Set<T>`, and for tuples of up to eight elements
+/// that each implement it. Builders use it as a bound on their `.build()`
+/// method, so that it can only be called once every required field is `Set`.
+pub trait Complete {
+	/// The values of the fields.
+	type Output;
+
+	/// Unwraps the values of all the fields.
+	fn complete(self) -> Self::Output;
+}
+
+impl<T> Complete for Set<T> {
+	type Output = T;
+
+	#[inline(always)]
+	fn complete(self) -> Self::Output {
+		self.0
+	}
+}
+
+macro_rules! complete {
+	($($t:ident),+) => {
+		impl<$($t),+> Complete for ($($t,)+)
+		where $($t: Complete),+
+		{
+			type Output = ($($t::Output,)+);
+
+			#[inline(always)]
+			#[allow(non_snake_case)]
+			fn complete(self) -> Self::Output {
+				let ($($t,)+) = self;
+				($($t.complete(),)+)
+			}
+		}
+	};
+}
+
+complete!(A);
+complete!(A, B);
+complete!(A, B, C);
+complete!(A, B, C, D);
+complete!(A, B, C, D, E);
+complete!(A, B, C, D, E, F);
+complete!(A, B, C, D, E, F, G);
+complete!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fields() {
+		assert!(!Field::<i32>::is_set(&Unset));
+		assert_eq!(Field::<i32>::into_option(Unset), None);
+		assert_eq!(Unset.unwrap_or(5), 5);
+		assert_eq!(Unset.unwrap_or_else(|| 6), 6);
+
+		assert!(Set(1).is_set());
+		assert_eq!(Set(1).as_option(), Some(&1));
+		assert_eq!(Set(1).unwrap_or(5), 1);
+		assert_eq!(Set(2).into_inner(), 2);
+	}
+
+	#[test]
+	fn completion() {
+		assert_eq!(Set('a').complete(), 'a');
+		assert_eq!((Set(1),).complete(), (1,));
+		assert_eq!(
+			(Set(1), Set("two"), (Set(3.0),)).complete(),
+			(1, "two", (3.0,))
+		);
+		assert_eq!(
+			(
+				Set(1),
+				Set(2),
+				Set(3),
+				Set(4),
+				Set(5),
+				Set(6),
+				Set(7),
+				Set(8)
+			)
+				.complete(),
+			(1, 2, 3, 4, 5, 6, 7, 8),
+		);
+	}
+}